@@ -1,3 +1,23 @@
 fn main() {
+    // Expose build metadata to `get_app_info`
+    println!(
+        "cargo:rustc-env=TARGET_TRIPLE={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+
+    // Prefer a commit provided by CI, otherwise ask git (absent in source tarballs)
+    let commit = std::env::var("VERGEN_GIT_SHA").ok().or_else(|| {
+        std::process::Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+    if let Some(commit) = commit.filter(|c| !c.is_empty()) {
+        println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+    }
+    println!("cargo:rerun-if-env-changed=VERGEN_GIT_SHA");
+
     tauri_build::build();
 }
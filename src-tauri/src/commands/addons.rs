@@ -1,8 +1,18 @@
-use tauri::Window;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Window};
 
-use crate::ddev::{run_ddev_command_async, run_ddev_command_streaming};
+use crate::ddev::{
+    run_ddev_command_async, run_ddev_command_streaming, run_ddev_command_streaming_with_input,
+};
 use crate::error::DdevError;
-use crate::types::{AddonRegistry, DdevJsonResponse, InstalledAddon};
+use crate::http::http_client;
+use crate::types::{
+    AddonCompatibility, AddonRegistry, AddonStats, AddonUpdateInfo, AddonVerified,
+    DdevJsonResponse, InstalledAddon,
+};
 
 /// List installed addons for a project
 #[tauri::command]
@@ -30,7 +40,7 @@ pub async fn list_installed_addons(project: String) -> Result<Vec<InstalledAddon
 /// Fetch addon registry from addons.ddev.com
 #[tauri::command]
 pub async fn fetch_addon_registry() -> Result<AddonRegistry, DdevError> {
-    let client = reqwest::Client::new();
+    let client = http_client()?;
     let response = client
         .get("https://addons.ddev.com/addons.json")
         .send()
@@ -53,18 +63,269 @@ pub async fn fetch_addon_registry() -> Result<AddonRegistry, DdevError> {
         .map_err(|e| DdevError::ParseError(format!("Failed to parse registry JSON: {}", e)))
 }
 
+/// How long fetched addon stats are reused before asking GitHub again
+const ADDON_STATS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Concurrent GitHub API requests per refresh
+const ADDON_STATS_CONCURRENCY: usize = 4;
+
+/// Back-off when GitHub rate-limits us without saying until when
+const DEFAULT_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+static ADDON_STATS_CACHE: Lazy<Mutex<HashMap<String, (Instant, AddonStats)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Set when GitHub reports the (unauthenticated) rate limit is exhausted
+static RATE_LIMITED_UNTIL: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+fn is_rate_limited() -> bool {
+    RATE_LIMITED_UNTIL
+        .lock()
+        .unwrap()
+        .is_some_and(|until| Instant::now() < until)
+}
+
+/// Remember when the rate limit resets, from GitHub's `x-ratelimit-reset` epoch header
+fn record_rate_limit(headers: &reqwest::header::HeaderMap) {
+    let backoff = headers
+        .get("x-ratelimit-reset")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .and_then(|reset| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+            Some(Duration::from_secs(reset.saturating_sub(now)))
+        })
+        .unwrap_or(DEFAULT_RATE_LIMIT_BACKOFF);
+    *RATE_LIMITED_UNTIL.lock().unwrap() = Some(Instant::now() + backoff);
+}
+
+/// Fetch one repository's stats; None on any failure (the caller keeps registry values)
+async fn fetch_addon_stats(client: &reqwest::Client, repo: &str) -> Option<AddonStats> {
+    if is_rate_limited() {
+        return None;
+    }
+
+    let response = client
+        .get(format!("https://api.github.com/repos/{}", repo))
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .ok()?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || (status == reqwest::StatusCode::FORBIDDEN
+            && response
+                .headers()
+                .get("x-ratelimit-remaining")
+                .is_some_and(|remaining| remaining == "0"))
+    {
+        record_rate_limit(response.headers());
+        return None;
+    }
+    if !status.is_success() {
+        return None;
+    }
+
+    response.json::<AddonStats>().await.ok()
+}
+
+/// Fetch live GitHub stars and last-updated time for addon repositories (`owner/repo`)
+/// Results are cached for an hour. Repositories that can't be fetched (network errors,
+/// rate limiting, unknown repos) are left out, so the UI keeps the registry values for them.
+#[tauri::command]
+pub async fn refresh_addon_stats(
+    repos: Vec<String>,
+) -> Result<HashMap<String, AddonStats>, DdevError> {
+    let mut stats = HashMap::new();
+    let mut to_fetch = Vec::new();
+    {
+        let cache = ADDON_STATS_CACHE.lock().unwrap();
+        for repo in repos {
+            let valid = repo.split_once('/').is_some_and(|(owner, name)| {
+                !owner.is_empty() && !name.is_empty() && !name.contains('/')
+            });
+            if !valid {
+                continue;
+            }
+            match cache.get(&repo) {
+                Some((fetched_at, cached)) if fetched_at.elapsed() < ADDON_STATS_TTL => {
+                    stats.insert(repo, cached.clone());
+                }
+                _ => to_fetch.push(repo),
+            }
+        }
+    }
+    to_fetch.sort();
+    to_fetch.dedup();
+
+    if to_fetch.is_empty() || is_rate_limited() {
+        return Ok(stats);
+    }
+
+    let client = http_client()?;
+    let permits = Arc::new(tokio::sync::Semaphore::new(ADDON_STATS_CONCURRENCY));
+    let handles: Vec<_> = to_fetch
+        .into_iter()
+        .map(|repo| {
+            let client = client.clone();
+            let permits = permits.clone();
+            tauri::async_runtime::spawn(async move {
+                let _permit = permits.acquire().await;
+                let fetched = fetch_addon_stats(&client, &repo).await;
+                (repo, fetched)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        if let Ok((repo, Some(fetched))) = handle.await {
+            ADDON_STATS_CACHE
+                .lock()
+                .unwrap()
+                .insert(repo.clone(), (Instant::now(), fetched.clone()));
+            stats.insert(repo, fetched);
+        }
+    }
+
+    Ok(stats)
+}
+
+/// How long `install_addon` waits for `ddev add-on list` when verifying an install
+const ADDON_VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Install an addon (streaming output)
+/// Once the install succeeds, checks in the background that the addon is now registered with
+/// the project and emits `addon-verified` (skipped if the installed list can't be read within
+/// `ADDON_VERIFY_TIMEOUT`). The install's own status doesn't wait for this check.
 /// Returns a process ID that can be used to cancel the command
 #[tauri::command]
 pub fn install_addon(window: Window, project: String, addon: String) -> Result<String, DdevError> {
-    run_ddev_command_streaming(
+    let emitter = window.clone();
+    let (verify_project, verify_addon) = (project.clone(), addon.clone());
+
+    run_ddev_command_streaming_with_input(
         window,
         "addon-install",
         &project,
         &["add-on", "get", &addon, "--project", &project],
+        move |outcome| {
+            if !outcome.success {
+                return;
+            }
+            tauri::async_runtime::spawn(async move {
+                let verified = tokio::time::timeout(
+                    ADDON_VERIFY_TIMEOUT,
+                    verify_addon_installed(verify_project.clone(), verify_addon.clone()),
+                )
+                .await;
+                if let Ok(Ok(installed)) = verified {
+                    let _ = emitter.emit(
+                        "addon-verified",
+                        AddonVerified {
+                            project: verify_project,
+                            addon: verify_addon,
+                            installed,
+                        },
+                    );
+                }
+            });
+        },
+    )
+}
+
+/// Check whether an addon is registered with a project, to catch installs that "succeed"
+/// without registering anything
+/// `addon` may be an `owner/repo` shorthand, a GitHub URL or just the repository name; it is
+/// matched against the installed addons' repositories
+#[tauri::command]
+pub async fn verify_addon_installed(project: String, addon: String) -> Result<bool, DdevError> {
+    let key = repository_key(&addon);
+    let installed = list_installed_addons(project).await?;
+
+    Ok(installed.iter().any(|installed| {
+        let repository = repository_key(&installed.repository);
+        repository == key
+            || (!key.contains('/')
+                && (repository.rsplit('/').next() == Some(key.as_str())
+                    || installed.name.eq_ignore_ascii_case(&key)))
+    }))
+}
+
+/// Update an installed addon to its latest release (streaming output)
+/// Re-running `ddev add-on get` on an installed addon replaces it with the latest version
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn update_addon(window: Window, project: String, addon: String) -> Result<String, DdevError> {
+    run_ddev_command_streaming_with_input(
+        window,
+        "addon-update",
+        &project,
+        &["add-on", "get", &addon, "--project", &project],
+        |_| {},
     )
 }
 
+/// Normalize an addon reference (`owner/repo`, GitHub URL, `.git` suffix) for comparison
+fn repository_key(repository: &str) -> String {
+    let repository = repository.trim().trim_end_matches('/');
+    let repository = repository.strip_suffix(".git").unwrap_or(repository);
+    let repository = ["https://github.com/", "http://github.com/", "github.com/"]
+        .iter()
+        .find_map(|prefix| repository.strip_prefix(prefix))
+        .unwrap_or(repository);
+    repository.to_lowercase()
+}
+
+/// Whether `latest` is a newer release than `installed`
+/// Compares as semver when both parse (ignoring a leading "v"), otherwise any difference counts
+fn is_newer_version(installed: Option<&str>, latest: &str) -> bool {
+    let parse = |v: &str| semver::Version::parse(v.trim().trim_start_matches('v')).ok();
+    match installed.filter(|v| !v.is_empty()) {
+        None => true,
+        Some(installed) => match (parse(installed), parse(latest)) {
+            (Some(installed), Some(latest)) => latest > installed,
+            _ => installed.trim() != latest.trim(),
+        },
+    }
+}
+
+/// List a project's installed addons that have a newer release in the addon registry
+/// Addons are matched to registry entries by repository; ones not in the registry are skipped
+#[tauri::command]
+pub async fn get_addon_updates(project: String) -> Result<Vec<AddonUpdateInfo>, DdevError> {
+    let installed = list_installed_addons(project).await?;
+    if installed.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let registry = fetch_addon_registry().await?;
+    let latest_versions: HashMap<String, String> = registry
+        .addons
+        .into_iter()
+        .filter_map(|addon| {
+            let tag = addon.tag_name.filter(|tag| !tag.is_empty())?;
+            Some((
+                repository_key(&format!("{}/{}", addon.user, addon.repo)),
+                tag,
+            ))
+        })
+        .collect();
+
+    Ok(installed
+        .into_iter()
+        .filter_map(|addon| {
+            let latest = latest_versions.get(&repository_key(&addon.repository))?;
+            is_newer_version(addon.version.as_deref(), latest).then(|| AddonUpdateInfo {
+                name: addon.name,
+                repository: addon.repository,
+                installed_version: addon.version,
+                latest_version: latest.clone(),
+            })
+        })
+        .collect())
+}
+
 /// Remove an addon (streaming output)
 /// Returns a process ID that can be used to cancel the command
 #[tauri::command]
@@ -76,3 +337,70 @@ pub fn remove_addon(window: Window, project: String, addon: String) -> Result<St
         &["add-on", "remove", &addon, "--project", &project],
     )
 }
+
+/// Check whether an addon (`owner/repo`) supports the installed DDEV version
+/// An empty or unparseable constraint is treated as compatible
+#[tauri::command]
+pub async fn check_addon_compatible(addon: String) -> Result<AddonCompatibility, DdevError> {
+    let registry = fetch_addon_registry().await?;
+    let constraint = registry
+        .addons
+        .iter()
+        .find(|a| format!("{}/{}", a.user, a.repo).eq_ignore_ascii_case(&addon))
+        .map(|a| a.ddev_version_constraint.trim().to_string())
+        .ok_or_else(|| {
+            DdevError::CommandFailed(format!("Addon not found in registry: {}", addon))
+        })?;
+
+    let version_output = run_ddev_command_async(&["--json-output", "version"]).await?;
+    let installed = serde_json::from_str::<DdevJsonResponse<serde_json::Value>>(&version_output)
+        .ok()
+        .and_then(|response| response.raw["DDEV version"].as_str().map(|v| v.to_string()))
+        .ok_or_else(|| DdevError::ParseError("Could not determine DDEV version".to_string()))?;
+
+    let requirement = semver::VersionReq::parse(&normalize_version_constraint(&constraint));
+    let version = semver::Version::parse(installed.trim_start_matches('v'));
+
+    let compatible = match (requirement, version) {
+        (Ok(requirement), Ok(mut version)) if !constraint.is_empty() => {
+            // Dev builds (e.g. v1.24.1-12-gabcdef) should match like their release
+            version.pre = semver::Prerelease::EMPTY;
+            requirement.matches(&version)
+        }
+        _ => true,
+    };
+
+    Ok(AddonCompatibility {
+        compatible,
+        constraint,
+        installed,
+    })
+}
+
+/// Convert a registry constraint (Go-style, e.g. ">= v1.23.0 < v2") to `semver` syntax
+fn normalize_version_constraint(constraint: &str) -> String {
+    let is_operator = |c: char| "<>=!~^".contains(c);
+    let mut comparators: Vec<String> = Vec::new();
+    let mut pending_op = String::new();
+
+    for token in constraint
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+    {
+        if token.chars().all(is_operator) {
+            pending_op.push_str(token);
+            continue;
+        }
+        let version_start = token.find(|c: char| !is_operator(c)).unwrap_or(0);
+        let (op, version) = token.split_at(version_start);
+        comparators.push(format!(
+            "{}{}{}",
+            pending_op,
+            op,
+            version.trim_start_matches('v')
+        ));
+        pending_op.clear();
+    }
+
+    comparators.join(", ")
+}
@@ -8,7 +8,10 @@ use crate::error::DdevError;
 use crate::process::{
     create_task_entry, generate_process_id, is_process_cancelled, remove_task_entry,
 };
-use crate::types::{CmsInstall, CmsInstallResult, CommandOutput, CommandStatus};
+use crate::settings::{load_settings, update_settings};
+use crate::types::{
+    CmsInstall, CmsInstallResult, CommandOutput, CommandStatus, NormalizedPath, PathCheck,
+};
 
 /// Check if a folder is empty (completely empty, no files at all)
 /// Composer create-project requires a truly empty folder
@@ -33,6 +36,247 @@ pub async fn check_folder_empty(path: String) -> Result<bool, DdevError> {
     Ok(entries.next().is_none())
 }
 
+/// Clean up a pasted project path: trims whitespace and surrounding quotes, expands `~`,
+/// resolves `.`/`..` and trailing separators, and makes relative paths absolute (from home)
+#[tauri::command]
+pub fn normalize_path(input: String) -> Result<NormalizedPath, DdevError> {
+    use std::path::{Component, PathBuf};
+
+    let mut trimmed = input.trim();
+    for quote in ['"', '\'', '`'] {
+        if trimmed.len() >= 2 && trimmed.starts_with(quote) && trimmed.ends_with(quote) {
+            trimmed = trimmed[1..trimmed.len() - 1].trim();
+            break;
+        }
+    }
+    if trimmed.is_empty() {
+        return Err(DdevError::CommandFailed("Path is empty".to_string()));
+    }
+
+    // Windows accepts both separators; normalize so paths display consistently
+    #[cfg(target_os = "windows")]
+    let trimmed = trimmed.replace('/', "\\");
+    #[cfg(not(target_os = "windows"))]
+    let trimmed = trimmed.to_string();
+
+    let home = dirs::home_dir()
+        .ok_or_else(|| DdevError::IoError("Could not determine home directory".to_string()))?;
+    let expanded = match trimmed.strip_prefix('~') {
+        Some("") => home.clone(),
+        Some(rest) if rest.starts_with(['/', '\\']) => home.join(&rest[1..]),
+        _ => PathBuf::from(&trimmed),
+    };
+    let absolute = if expanded.is_absolute() {
+        expanded
+    } else {
+        home.join(expanded)
+    };
+
+    // Lexical cleanup; canonicalize() would require the path to exist
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    Ok(NormalizedPath {
+        path: normalized.to_string_lossy().to_string(),
+        exists: normalized.exists(),
+        is_dir: normalized.is_dir(),
+    })
+}
+
+/// System folders projects must never be created in (or below)
+#[cfg(target_os = "windows")]
+const PROTECTED_ROOTS: &[&str] = &[
+    "C:\\Windows",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+    "C:\\ProgramData",
+];
+#[cfg(not(target_os = "windows"))]
+const PROTECTED_ROOTS: &[&str] = &[
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/proc",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/System",
+    "/Library",
+    "/Applications",
+    "/private/etc",
+];
+
+/// Comparable form of a normalized path (Windows paths are case-insensitive)
+fn path_key(path: &str) -> std::path::PathBuf {
+    if cfg!(target_os = "windows") {
+        std::path::PathBuf::from(path.to_lowercase())
+    } else {
+        std::path::PathBuf::from(path)
+    }
+}
+
+/// Check that a folder picked for a new project is inside the user's home or one of the
+/// allowed project roots from settings, and not a filesystem root or system folder
+#[tauri::command]
+pub fn is_path_allowed(path: String) -> Result<PathCheck, DdevError> {
+    let denied = |reason: String| {
+        Ok(PathCheck {
+            allowed: false,
+            reason: Some(reason),
+        })
+    };
+
+    let normalized = normalize_path(path)?.path;
+    let target = path_key(&normalized);
+
+    if target.parent().is_none() {
+        return denied(format!("{} is a filesystem root", normalized));
+    }
+    if let Some(root) = PROTECTED_ROOTS
+        .iter()
+        .find(|root| target.starts_with(path_key(root)))
+    {
+        return denied(format!(
+            "{} is inside the system folder {}",
+            normalized, root
+        ));
+    }
+
+    let home = dirs::home_dir()
+        .ok_or_else(|| DdevError::IoError("Could not determine home directory".to_string()))?;
+    let home = path_key(&home.to_string_lossy());
+    if target == home {
+        return denied(
+            "Choose a folder inside your home folder, not the home folder itself".to_string(),
+        );
+    }
+
+    let in_allowed_root = load_settings()
+        .allowed_project_roots
+        .iter()
+        .any(|root| target.starts_with(path_key(root)));
+    if target.starts_with(&home) || in_allowed_root {
+        return Ok(PathCheck {
+            allowed: true,
+            reason: None,
+        });
+    }
+
+    denied(format!(
+        "{} is outside your home folder; add its parent as an allowed project root to use it",
+        normalized
+    ))
+}
+
+/// Set the extra folders (besides home) where new projects may be created
+#[tauri::command]
+pub fn set_allowed_project_roots(roots: Vec<String>) -> Result<(), DdevError> {
+    let mut normalized = Vec::new();
+    for root in roots {
+        let root = normalize_path(root)?.path;
+        if !normalized.contains(&root) {
+            normalized.push(root);
+        }
+    }
+
+    update_settings(|settings| settings.allowed_project_roots = normalized)
+}
+
+/// Conventional layout per project type: (type, expected docroot, file expected in the docroot)
+const LAYOUT_EXPECTATIONS: &[(&str, &str, &str)] = &[
+    ("drupal", "web", "index.php"),
+    ("drupal9", "web", "index.php"),
+    ("drupal10", "web", "index.php"),
+    ("drupal11", "web", "index.php"),
+    ("drupal7", "", "index.php"),
+    ("backdrop", "", "index.php"),
+    ("laravel", "public", "index.php"),
+    ("symfony", "public", "index.php"),
+    ("shopware6", "public", "index.php"),
+    ("silverstripe", "public", "index.php"),
+    ("typo3", "public", "index.php"),
+    ("craftcms", "web", "index.php"),
+    ("magento2", "pub", "index.php"),
+    ("cakephp", "webroot", "index.php"),
+    ("wordpress", "", "wp-settings.php"),
+];
+
+/// Check a project's docroot against its type's usual layout (advisory, never blocks)
+/// Returns human-readable warnings; an empty list means nothing looks off. File checks only
+/// run once the docroot exists, so a not-yet-populated project only gets convention warnings.
+#[tauri::command]
+pub fn validate_project_layout(
+    approot: String,
+    project_type: String,
+    docroot: String,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let docroot = docroot.trim().trim_matches(['/', '\\']).to_string();
+    let docroot_path = std::path::Path::new(&approot).join(&docroot);
+
+    if std::path::Path::new(&docroot).is_absolute() || docroot.split(['/', '\\']).any(|p| p == "..")
+    {
+        warnings.push(format!(
+            "Docroot \"{}\" must be a folder inside the project",
+            docroot
+        ));
+        return warnings;
+    }
+
+    if !docroot.is_empty() && std::path::Path::new(&approot).is_dir() && !docroot_path.is_dir() {
+        warnings.push(format!("Docroot folder \"{}\" doesn't exist yet", docroot));
+    }
+
+    let Some((_, expected_docroot, entry_file)) = LAYOUT_EXPECTATIONS
+        .iter()
+        .find(|(t, _, _)| *t == project_type)
+    else {
+        return warnings;
+    };
+
+    if docroot != *expected_docroot {
+        let describe = |d: &str| {
+            if d.is_empty() {
+                "the project root".to_string()
+            } else {
+                format!("\"{}\"", d)
+            }
+        };
+        warnings.push(format!(
+            "{} projects usually use {} as docroot, not {}",
+            project_type,
+            describe(expected_docroot),
+            describe(&docroot)
+        ));
+    }
+
+    if docroot_path.is_dir() && !docroot_path.join(entry_file).is_file() {
+        let has_files = std::fs::read_dir(&docroot_path)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false);
+        if has_files {
+            warnings.push(format!(
+                "No {} in the docroot; is \"{}\" the right folder for a {} project?",
+                entry_file,
+                if docroot.is_empty() { "." } else { &docroot },
+                project_type
+            ));
+        }
+    }
+
+    warnings
+}
+
 /// Check if composer is installed
 #[tauri::command]
 pub async fn check_composer_installed() -> Result<bool, DdevError> {
@@ -99,10 +343,7 @@ fn install_cms(
             if let Some(package) = &cms.package {
                 let _ = window.emit(
                     "command-output",
-                    CommandOutput {
-                        line: format!("Installing {} via Composer...", package),
-                        stream: "stdout".to_string(),
-                    },
+                    CommandOutput::new(format!("Installing {} via Composer...", package), "stdout"),
                 );
                 match run_streaming_command(
                     window,
@@ -121,10 +362,10 @@ fn install_cms(
             } else {
                 let _ = window.emit(
                     "command-output",
-                    CommandOutput {
-                        line: "Error: No composer package specified".to_string(),
-                        stream: "stderr".to_string(),
-                    },
+                    CommandOutput::new(
+                        "Error: No composer package specified".to_string(),
+                        "stderr",
+                    ),
                 );
                 CmsInstallResult::Failed
             }
@@ -141,10 +382,7 @@ fn install_cms(
             if wp_available {
                 let _ = window.emit(
                     "command-output",
-                    CommandOutput {
-                        line: "Installing WordPress via WP-CLI...".to_string(),
-                        stream: "stdout".to_string(),
-                    },
+                    CommandOutput::new("Installing WordPress via WP-CLI...".to_string(), "stdout"),
                 );
                 match run_streaming_command(
                     window,
@@ -164,10 +402,10 @@ fn install_cms(
                 // Download from wordpress.org
                 let _ = window.emit(
                     "command-output",
-                    CommandOutput {
-                        line: "Downloading WordPress from wordpress.org...".to_string(),
-                        stream: "stdout".to_string(),
-                    },
+                    CommandOutput::new(
+                        "Downloading WordPress from wordpress.org...".to_string(),
+                        "stdout",
+                    ),
                 );
 
                 // Download latest.zip
@@ -190,10 +428,7 @@ fn install_cms(
                 // Extract zip
                 let _ = window.emit(
                     "command-output",
-                    CommandOutput {
-                        line: "Extracting WordPress...".to_string(),
-                        stream: "stdout".to_string(),
-                    },
+                    CommandOutput::new("Extracting WordPress...".to_string(), "stdout"),
                 );
 
                 match run_streaming_command(
@@ -217,10 +452,10 @@ fn install_cms(
                     // Move all files from wordpress/ to current directory
                     let _ = window.emit(
                         "command-output",
-                        CommandOutput {
-                            line: "Moving WordPress files to project root...".to_string(),
-                            stream: "stdout".to_string(),
-                        },
+                        CommandOutput::new(
+                            "Moving WordPress files to project root...".to_string(),
+                            "stdout",
+                        ),
                     );
 
                     // Use shell to move files including hidden ones
@@ -238,10 +473,7 @@ fn install_cms(
                         Ok(false) => {
                             let _ = window.emit(
                                 "command-output",
-                                CommandOutput {
-                                    line: "Warning: Could not move some WordPress files".to_string(),
-                                    stream: "stderr".to_string(),
-                                },
+                                CommandOutput::new("Warning: Could not move some WordPress files".to_string(), "stderr"),
                             );
                         }
                         Err(_) => return CmsInstallResult::Cancelled,
@@ -257,44 +489,25 @@ fn install_cms(
         _ => {
             let _ = window.emit(
                 "command-output",
-                CommandOutput {
-                    line: format!("Unknown installation type: {}", cms.install_type),
-                    stream: "stderr".to_string(),
-                },
+                CommandOutput::new(
+                    format!("Unknown installation type: {}", cms.install_type),
+                    "stderr",
+                ),
             );
             CmsInstallResult::Failed
         }
     }
 }
 
-/// Create a new DDEV project (streaming output)
-#[tauri::command]
-#[allow(clippy::too_many_arguments)]
-pub fn create_project(
-    window: Window,
-    path: String,
-    name: String,
+/// Build the `ddev config` arguments for a new project
+fn build_config_args(
+    name: &str,
     project_type: Option<String>,
     php_version: Option<String>,
     database: Option<String>,
     webserver: Option<String>,
     docroot: Option<String>,
-    auto_start: bool,
-    cms_install: Option<String>,
-) -> Result<String, DdevError> {
-    let process_id = generate_process_id();
-    let command_name = "config".to_string();
-    let project_name = name.clone();
-    let ddev_cmd = get_ddev_command();
-    let enhanced_path = get_enhanced_path();
-    let process_id_clone = process_id.clone();
-
-    // Parse CMS install instruction if provided
-    let cms_install_parsed: Option<CmsInstall> = cms_install
-        .as_ref()
-        .and_then(|json| serde_json::from_str(json).ok());
-
-    // Build the ddev config arguments
+) -> Vec<String> {
     let mut args = vec![
         "config".to_string(),
         format!("--project-name={}", name),
@@ -332,6 +545,128 @@ pub fn create_project(
         }
     }
 
+    args
+}
+
+/// Run the `ddev config` and optional `ddev start` phases of project creation,
+/// then emit the final status. Skips config when `config_args` is None.
+#[allow(clippy::too_many_arguments)]
+fn run_config_and_start(
+    window: &Window,
+    ddev_cmd: &str,
+    config_args: Option<&[String]>,
+    auto_start: bool,
+    path: &str,
+    enhanced_path: &str,
+    process_id: &str,
+    command_name: &str,
+    project_name: &str,
+) {
+    let finish = |status: &str, message: &str| {
+        // Clean up registry entry
+        remove_task_entry(process_id);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name.to_string(),
+                project: project_name.to_string(),
+                status: status.to_string(),
+                message: Some(message.to_string()),
+                process_id: None,
+            },
+        );
+    };
+
+    // Run ddev config using run_streaming_command for proper cancellation support
+    if let Some(args) = config_args {
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        match run_streaming_command(
+            window,
+            ddev_cmd,
+            &args_refs,
+            path,
+            enhanced_path,
+            Some(process_id),
+            command_name,
+            project_name,
+        ) {
+            Ok(true) => {}
+            Ok(false) => {
+                finish("error", "Failed to create project");
+                return;
+            }
+            Err(_) => {
+                return; // Cancelled - cancel_command already emitted the status
+            }
+        }
+    }
+
+    if auto_start {
+        // Check if cancelled before starting
+        if is_process_cancelled(process_id) {
+            return;
+        }
+
+        let _ = window.emit(
+            "command-output",
+            CommandOutput::new("Starting project...".to_string(), "stdout"),
+        );
+
+        if run_streaming_command(
+            window,
+            ddev_cmd,
+            &["start"],
+            path,
+            enhanced_path,
+            Some(process_id),
+            command_name,
+            project_name,
+        )
+        .is_err()
+        {
+            return; // Cancelled
+        }
+    }
+
+    finish("finished", "Project created successfully");
+}
+
+/// Create a new DDEV project (streaming output)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn create_project(
+    window: Window,
+    path: String,
+    name: String,
+    project_type: Option<String>,
+    php_version: Option<String>,
+    database: Option<String>,
+    webserver: Option<String>,
+    docroot: Option<String>,
+    auto_start: bool,
+    cms_install: Option<String>,
+) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let command_name = "config".to_string();
+    let project_name = name.clone();
+    let ddev_cmd = get_ddev_command();
+    let enhanced_path = get_enhanced_path();
+    let process_id_clone = process_id.clone();
+
+    // Parse CMS install instruction if provided
+    let cms_install_parsed: Option<CmsInstall> = cms_install
+        .as_ref()
+        .and_then(|json| serde_json::from_str(json).ok());
+
+    let args = build_config_args(
+        &name,
+        project_type,
+        php_version,
+        database,
+        webserver,
+        docroot,
+    );
+
     // Create an entry in the registry for this multi-step task
     // Individual commands will register their child processes for cancellation support
     create_task_entry(&process_id, &command_name, &project_name);
@@ -414,83 +749,108 @@ pub fn create_project(
             return;
         }
 
-        // Run ddev config using run_streaming_command for proper cancellation support
-        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        match run_streaming_command(
+        run_config_and_start(
             &window,
             &ddev_cmd,
-            &args_refs,
+            Some(&args),
+            auto_start,
             &path,
             &enhanced_path,
-            Some(&process_id_clone),
+            &process_id_clone,
             &command_name,
             &project_name,
-        ) {
-            Ok(true) => {
-                // Config succeeded, check if we need to auto-start
-                if auto_start {
-                    // Check if cancelled before starting
-                    if check_cancelled() {
-                        return;
-                    }
+        );
+    });
 
-                    let _ = window.emit(
-                        "command-output",
-                        CommandOutput {
-                            line: "Starting project...".to_string(),
-                            stream: "stdout".to_string(),
-                        },
-                    );
+    Ok(process_id)
+}
 
-                    // Run ddev start using run_streaming_command
-                    match run_streaming_command(
-                        &window,
-                        &ddev_cmd,
-                        &["start"],
-                        &path,
-                        &enhanced_path,
-                        Some(&process_id_clone),
-                        &command_name,
-                        &project_name,
-                    ) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            return; // Cancelled
-                        }
-                    }
-                }
+/// Resume a failed project creation without redoing the folder and CMS steps
+/// `from_step` is "config" (re-run `ddev config`, then start if `auto_start`) or
+/// "start" (only run `ddev start`, which requires an existing `.ddev/config.yaml`)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn resume_create(
+    window: Window,
+    path: String,
+    name: String,
+    project_type: Option<String>,
+    php_version: Option<String>,
+    database: Option<String>,
+    webserver: Option<String>,
+    docroot: Option<String>,
+    auto_start: bool,
+    from_step: String,
+) -> Result<String, DdevError> {
+    if !std::path::Path::new(&path).is_dir() {
+        return Err(DdevError::CommandFailed(format!(
+            "Project folder {} doesn't exist; start the creation over",
+            path
+        )));
+    }
 
-                // Clean up registry entry
-                remove_task_entry(&process_id_clone);
-                let _ = window.emit(
-                    "command-status",
-                    CommandStatus {
-                        command: command_name,
-                        project: project_name,
-                        status: "finished".to_string(),
-                        message: Some("Project created successfully".to_string()),
-                        process_id: None,
-                    },
-                );
-            }
-            Ok(false) => {
-                // Clean up registry entry
-                remove_task_entry(&process_id_clone);
-                let _ = window.emit(
-                    "command-status",
-                    CommandStatus {
-                        command: command_name,
-                        project: project_name,
-                        status: "error".to_string(),
-                        message: Some("Failed to create project".to_string()),
-                        process_id: None,
-                    },
-                );
-            }
-            Err(_) => {
-                // Cancelled - cancel_command already emitted the status
-            }
+    let is_configured = std::path::Path::new(&path)
+        .join(".ddev")
+        .join("config.yaml")
+        .is_file();
+
+    let config_args = match from_step.as_str() {
+        "config" => Some(build_config_args(
+            &name,
+            project_type,
+            php_version,
+            database,
+            webserver,
+            docroot,
+        )),
+        "start" if is_configured => None,
+        "start" => {
+            return Err(DdevError::CommandFailed(
+                "Project has no .ddev/config.yaml yet; resume from the config step".to_string(),
+            ))
+        }
+        other => {
+            return Err(DdevError::CommandFailed(format!(
+                "Unknown create step: {} (expected \"config\" or \"start\")",
+                other
+            )))
         }
+    };
+    // Resuming at start always starts, regardless of the original auto-start choice
+    let auto_start = auto_start || config_args.is_none();
+
+    let process_id = generate_process_id();
+    let command_name = "config".to_string();
+    let project_name = name.clone();
+    let ddev_cmd = get_ddev_command();
+    let enhanced_path = get_enhanced_path();
+    let process_id_clone = process_id.clone();
+
+    create_task_entry(&process_id, &command_name, &project_name);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project_name.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Resuming project creation at {}", from_step)),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        run_config_and_start(
+            &window,
+            &ddev_cmd,
+            config_args.as_deref(),
+            auto_start,
+            &path,
+            &enhanced_path,
+            &process_id_clone,
+            &command_name,
+            &project_name,
+        );
     });
 
     Ok(process_id)
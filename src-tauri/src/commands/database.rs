@@ -1,7 +1,26 @@
-use tauri::Window;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use tauri::{Emitter, Window};
 
-use crate::ddev::run_ddev_command_streaming;
+use crate::commands::{ddev_command_line, launch_in_terminal};
+use crate::ddev::{
+    get_ddev_base_args, get_ddev_command, get_enhanced_path, run_ddev_command_streaming,
+    run_ddev_json_command_async, run_ddev_json_command_in_dir_async, run_streaming_command,
+};
 use crate::error::DdevError;
+use crate::process::{
+    create_task_entry, generate_process_id, is_process_cancelled, register_child_process,
+    remove_task_entry, take_child_process, write_child_stdin, PROCESS_REGISTRY,
+};
+use crate::types::{
+    CommandOutput, CommandStatus, DbFileInfo, DdevDatabaseInfo, DdevProjectDetails, TaskPhase,
+};
+
+/// How much of the (decompressed) dump to scan for dialect hints
+const DB_FILE_PEEK_BYTES: u64 = 64 * 1024;
 
 /// Select a database file to import (.sql, .sql.gz, .sql.tar.gz, .zip)
 #[tauri::command]
@@ -49,20 +68,24 @@ pub async fn select_export_destination(
 }
 
 /// Import a database file (streaming output)
+/// With `create_if_missing`, a named target database is created first (engine-aware),
+/// so importing into a secondary database of a multi-db project can't fail half-way
+/// with "unknown database"
 #[tauri::command]
-pub fn import_db(
+pub async fn import_db(
     window: Window,
     project: String,
     file_path: String,
     database: Option<String>,
     no_drop: Option<bool>,
+    create_if_missing: Option<bool>,
 ) -> Result<String, DdevError> {
+    let target_db = database.filter(|db| !db.is_empty());
+
     let mut args = vec!["import-db".to_string(), format!("--file={}", file_path)];
 
-    if let Some(db) = database {
-        if !db.is_empty() {
-            args.push(format!("--database={}", db));
-        }
+    if let Some(db) = &target_db {
+        args.push(format!("--database={}", db));
     }
 
     if no_drop.unwrap_or(false) {
@@ -72,7 +95,363 @@ pub fn import_db(
     args.push(project.clone());
 
     let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    run_ddev_command_streaming(window, "import-db", &project, &args_refs)
+
+    match target_db {
+        Some(db) if create_if_missing.unwrap_or(false) => {
+            if !is_valid_database_name(&db) {
+                return Err(DdevError::CommandFailed(format!(
+                    "Invalid database name: {} (use letters, numbers and underscores)",
+                    db
+                )));
+            }
+
+            let details: DdevProjectDetails =
+                run_ddev_json_command_async(&["describe", &project]).await?;
+            let database_type = details
+                .dbinfo
+                .map(|info| info.database_type)
+                .or(details.database_type)
+                .unwrap_or_else(|| "mariadb".to_string());
+
+            import_db_into_new_database(window, project, details.approot, db, database_type, args)
+        }
+        _ => run_ddev_command_streaming(window, "import-db", &project, &args_refs),
+    }
+}
+
+/// Open an interactive database shell (`ddev mysql` or `ddev psql`) in the OS terminal
+#[tauri::command]
+pub async fn open_db_shell(approot: String, database: Option<String>) -> Result<(), DdevError> {
+    let details: DdevProjectDetails =
+        run_ddev_json_command_in_dir_async(&["describe"], &approot).await?;
+
+    if details.status != "running" {
+        return Err(DdevError::CommandFailed(format!(
+            "Project {} is not running; start it to open a database shell",
+            details.name
+        )));
+    }
+
+    let database_type = details
+        .dbinfo
+        .map(|info| info.database_type)
+        .or(details.database_type)
+        .unwrap_or_else(|| "mariadb".to_string());
+
+    let database_arg = match database.filter(|db| !db.is_empty()) {
+        Some(db) if !is_valid_database_name(&db) => {
+            return Err(DdevError::CommandFailed(format!(
+                "Invalid database name: {} (use letters, numbers and underscores)",
+                db
+            )));
+        }
+        Some(db) if database_type == "postgres" => Some(format!("--dbname={}", db)),
+        Some(db) => Some(format!("--database={}", db)),
+        None => None,
+    };
+
+    let mut args = vec![if database_type == "postgres" {
+        "psql"
+    } else {
+        "mysql"
+    }];
+    if let Some(arg) = &database_arg {
+        args.push(arg);
+    }
+
+    launch_in_terminal(&approot, &ddev_command_line(&args))
+}
+
+/// Start an in-app database console (`ddev mysql` or `ddev psql`) for a running project
+/// Output streams as `command-output` events; send statements with `send_db_console_input`
+/// and end the session with `cancel_command`.
+/// Returns a process ID identifying the console session
+#[tauri::command]
+pub async fn open_db_console(window: Window, approot: String) -> Result<String, DdevError> {
+    let details: DdevProjectDetails =
+        run_ddev_json_command_in_dir_async(&["describe"], &approot).await?;
+
+    if details.status != "running" {
+        return Err(DdevError::CommandFailed(format!(
+            "Project {} is not running; start it to open a database console",
+            details.name
+        )));
+    }
+
+    let database_type = details
+        .dbinfo
+        .map(|info| info.database_type)
+        .or(details.database_type)
+        .unwrap_or_else(|| "mariadb".to_string());
+
+    let mut args = get_ddev_base_args();
+    if database_type == "postgres" {
+        args.push("psql");
+    } else {
+        // Without a terminal the mysql client prints bare tab-separated rows
+        args.extend_from_slice(&["mysql", "--table"]);
+    }
+
+    let command_name = "db-console".to_string();
+    let project = details.name;
+    let process_id = generate_process_id();
+
+    let mut child = Command::new(get_ddev_command())
+        .args(&args)
+        .current_dir(&approot)
+        .env("PATH", get_enhanced_path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| DdevError::IoError(format!("Failed to start database console: {}", e)))?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    register_child_process(&process_id, child, &command_name, &project);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Opened {} console", database_type)),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    let process_id_clone = process_id.clone();
+    thread::spawn(move || {
+        let readers: Vec<_> = [
+            stdout.map(|out| (Box::new(out) as Box<dyn Read + Send>, "stdout")),
+            stderr.map(|err| (Box::new(err) as Box<dyn Read + Send>, "stderr")),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(stream, name)| {
+            let window = window.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                    let _ = window.emit("command-output", CommandOutput::new(line, name));
+                }
+            })
+        })
+        .collect();
+
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        // Cancelled - cancel_command already killed the child and emitted the status
+        let Some(mut child) = take_child_process(&process_id_clone) else {
+            return;
+        };
+        let success = child.wait().map(|s| s.success()).unwrap_or(false);
+        remove_task_entry(&process_id_clone);
+
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project,
+                status: if success { "finished" } else { "error" }.to_string(),
+                message: Some(if success {
+                    "Database console closed".to_string()
+                } else {
+                    "Database console exited with an error".to_string()
+                }),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
+
+/// Send one line of input (a statement or client command) to a database console session
+#[tauri::command]
+pub fn send_db_console_input(process_id: String, line: String) -> Result<(), DdevError> {
+    let is_console = PROCESS_REGISTRY
+        .lock()
+        .unwrap()
+        .get(&process_id)
+        .is_some_and(|entry| entry.command == "db-console");
+    if !is_console {
+        return Err(DdevError::CommandFailed(format!(
+            "Database console {} is not running",
+            process_id
+        )));
+    }
+
+    write_child_stdin(&process_id, &format!("{}\n", line))
+}
+
+/// Placeholder returned instead of the database password unless it is explicitly revealed
+const MASKED_PASSWORD: &str = "****";
+
+/// Get a project's database credentials with the password masked unless `reveal` is true
+#[tauri::command]
+pub async fn get_db_credentials(name: String, reveal: bool) -> Result<DdevDatabaseInfo, DdevError> {
+    let mut dbinfo = get_dbinfo(&name).await?;
+    if !reveal {
+        dbinfo.password = MASKED_PASSWORD.to_string();
+    }
+    Ok(dbinfo)
+}
+
+/// Copy a project's database password to the clipboard without returning it to the webview
+#[tauri::command]
+pub async fn copy_db_password(app: tauri::AppHandle, name: String) -> Result<(), DdevError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let dbinfo = get_dbinfo(&name).await?;
+    app.clipboard()
+        .write_text(dbinfo.password)
+        .map_err(|e| DdevError::IoError(format!("Failed to write to clipboard: {}", e)))
+}
+
+/// Get the `dbinfo` section of `ddev describe`
+async fn get_dbinfo(name: &str) -> Result<DdevDatabaseInfo, DdevError> {
+    let details: DdevProjectDetails = run_ddev_json_command_async(&["describe", name]).await?;
+    details
+        .dbinfo
+        .ok_or_else(|| DdevError::CommandFailed(format!("Project {} has no database", name)))
+}
+
+/// Database names are interpolated into SQL, so only allow plain identifiers
+fn is_valid_database_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 63
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Build the ddev args that create a database if it doesn't exist yet
+fn ensure_database_args(database_type: &str, db: &str) -> Vec<String> {
+    if database_type == "postgres" {
+        // Postgres has no CREATE DATABASE IF NOT EXISTS, so check pg_database first
+        let script = format!(
+            "psql -U db -tAc \"SELECT 1 FROM pg_database WHERE datname='{db}'\" postgres \
+             | grep -q 1 || createdb -U db {db}"
+        );
+        vec![
+            "exec".to_string(),
+            "-s".to_string(),
+            "db".to_string(),
+            "sh".to_string(),
+            "-c".to_string(),
+            script,
+        ]
+    } else {
+        vec![
+            "mysql".to_string(),
+            "-e".to_string(),
+            format!("CREATE DATABASE IF NOT EXISTS `{}`", db),
+        ]
+    }
+}
+
+/// Create the target database, then import into it (multi-step task)
+fn import_db_into_new_database(
+    window: Window,
+    project: String,
+    approot: String,
+    db: String,
+    database_type: String,
+    import_args: Vec<String>,
+) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let command_name = "import-db".to_string();
+    let ddev_cmd = get_ddev_command();
+    let enhanced_path = get_enhanced_path();
+    let process_id_clone = process_id.clone();
+
+    // Prefix each step with the base args ("ddev" when running through WSL)
+    let with_base_args = |args: Vec<String>| -> Vec<String> {
+        let mut full_args: Vec<String> =
+            get_ddev_base_args().iter().map(|s| s.to_string()).collect();
+        full_args.extend(args);
+        full_args
+    };
+    let ensure_args = with_base_args(ensure_database_args(&database_type, &db));
+    let import_args = with_base_args(import_args);
+
+    create_task_entry(&process_id, &command_name, &project);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Importing into database {}", db)),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let finish = |status: &str, message: String| {
+            remove_task_entry(&process_id_clone);
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name.clone(),
+                    project: project.clone(),
+                    status: status.to_string(),
+                    message: Some(message),
+                    process_id: None,
+                },
+            );
+        };
+
+        // Step 1: Make sure the target database exists
+        let _ = window.emit(
+            "command-output",
+            CommandOutput::new(format!("Ensuring database {} exists...", db), "stdout"),
+        );
+
+        let ensure_refs: Vec<&str> = ensure_args.iter().map(|s| s.as_str()).collect();
+        match run_streaming_command(
+            &window,
+            &ddev_cmd,
+            &ensure_refs,
+            &approot,
+            &enhanced_path,
+            Some(&process_id_clone),
+            &command_name,
+            &project,
+        ) {
+            Ok(true) => {}
+            Ok(false) => {
+                finish("error", format!("Failed to create database {}", db));
+                return;
+            }
+            Err(_) => return, // Cancelled - cancel_command already emitted the status
+        }
+
+        if is_process_cancelled(&process_id_clone) {
+            return;
+        }
+
+        // Step 2: Import into it
+        let import_refs: Vec<&str> = import_args.iter().map(|s| s.as_str()).collect();
+        match run_streaming_command(
+            &window,
+            &ddev_cmd,
+            &import_refs,
+            &approot,
+            &enhanced_path,
+            Some(&process_id_clone),
+            &command_name,
+            &project,
+        ) {
+            Ok(true) => finish("finished", "Command completed successfully".to_string()),
+            Ok(false) => finish("error", "Command failed".to_string()),
+            Err(_) => {} // Cancelled
+        }
+    });
+
+    Ok(process_id)
 }
 
 /// Export database to file (streaming output)
@@ -106,3 +485,276 @@ pub fn export_db(
     let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     run_ddev_command_streaming(window, "export-db", &project, &args_refs)
 }
+
+/// Deletes a temporary dump when dropped, so it's cleaned up on every exit path
+struct TempDump(PathBuf);
+
+impl Drop for TempDump {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Copy one project's database into another (multi-step task with `task-phase` events)
+/// Starts either project if it isn't running, exports the source database to a temporary
+/// dump and imports it into the target, replacing the target's database. The dump goes in
+/// the source's `.ddev` folder and both steps run from the source approot with a relative
+/// `--file`, so it resolves the same when ddev runs through WSL (where the host's temp dir
+/// doesn't exist).
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn copy_database(
+    window: Window,
+    source_project: String,
+    source_approot: String,
+    target_project: String,
+    target_approot: String,
+) -> Result<String, DdevError> {
+    if source_project == target_project {
+        return Err(DdevError::CommandFailed(
+            "Source and target must be different projects".to_string(),
+        ));
+    }
+
+    let process_id = generate_process_id();
+    let command_name = "copy-db".to_string();
+    let ddev_cmd = get_ddev_command();
+    let enhanced_path = get_enhanced_path();
+    let process_id_clone = process_id.clone();
+    let dump_file = format!(".ddev/.ddev-manager-copy-{}.sql.gz", process_id);
+    let dump = TempDump(std::path::Path::new(&source_approot).join(&dump_file));
+
+    // Prefix each step with the base args ("ddev" when running through WSL)
+    let with_base_args = |args: &[&str]| -> Vec<String> {
+        let mut full_args: Vec<String> =
+            get_ddev_base_args().iter().map(|s| s.to_string()).collect();
+        full_args.extend(args.iter().map(|s| s.to_string()));
+        full_args
+    };
+    let dump_arg = format!("--file={}", dump_file);
+    let export_args = with_base_args(&["export-db", &dump_arg, &source_project]);
+    let import_args = with_base_args(&["import-db", &dump_arg, &target_project]);
+
+    create_task_entry(&process_id, &command_name, &target_project);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: target_project.clone(),
+            status: "started".to_string(),
+            message: Some(format!(
+                "Copying database from {} to {}",
+                source_project, target_project
+            )),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let _dump = dump;
+        let emit_phase = |phase: &str, message: String| {
+            let _ = window.emit(
+                "task-phase",
+                TaskPhase {
+                    process_id: process_id_clone.clone(),
+                    command: command_name.clone(),
+                    project: target_project.clone(),
+                    phase: phase.to_string(),
+                    message,
+                },
+            );
+        };
+        let finish = |status: &str, message: String| {
+            remove_task_entry(&process_id_clone);
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name.clone(),
+                    project: target_project.clone(),
+                    status: status.to_string(),
+                    message: Some(message),
+                    process_id: None,
+                },
+            );
+        };
+        // Run one step; Some(true) on success, Some(false) on failure, None when cancelled
+        let run_step = |args: &[String], cwd: &str| -> Option<bool> {
+            let refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+            run_streaming_command(
+                &window,
+                &ddev_cmd,
+                &refs,
+                cwd,
+                &enhanced_path,
+                Some(&process_id_clone),
+                &command_name,
+                &target_project,
+            )
+            .ok()
+        };
+
+        // Step 1: Make sure both projects are running
+        emit_phase(
+            "start",
+            "Checking that both projects are running".to_string(),
+        );
+        for (name, approot) in [
+            (&source_project, &source_approot),
+            (&target_project, &target_approot),
+        ] {
+            let running = tauri::async_runtime::block_on(run_ddev_json_command_async::<
+                DdevProjectDetails,
+            >(&["describe", name]))
+            .is_ok_and(|details| details.status == "running");
+            if running {
+                continue;
+            }
+
+            match run_step(&with_base_args(&["start", name]), approot) {
+                Some(true) => {}
+                Some(false) => {
+                    finish("error", format!("Failed to start {}", name));
+                    return;
+                }
+                None => return, // Cancelled - cancel_command already emitted the status
+            }
+        }
+
+        // Step 2: Export the source database
+        emit_phase(
+            "export",
+            format!("Exporting the {} database", source_project),
+        );
+        match run_step(&export_args, &source_approot) {
+            Some(true) => {}
+            Some(false) => {
+                finish(
+                    "error",
+                    format!("Failed to export the {} database", source_project),
+                );
+                return;
+            }
+            None => return,
+        }
+
+        if is_process_cancelled(&process_id_clone) {
+            return;
+        }
+
+        // Step 3: Import it into the target, from the source approot where the dump is
+        emit_phase("import", format!("Importing into {}", target_project));
+        match run_step(&import_args, &source_approot) {
+            Some(true) => finish(
+                "finished",
+                format!(
+                    "Copied the {} database to {}",
+                    source_project, target_project
+                ),
+            ),
+            Some(false) => finish(
+                "error",
+                format!("Failed to import the database into {}", target_project),
+            ),
+            None => {}
+        }
+    });
+
+    Ok(process_id)
+}
+
+/// Peek at a database dump to guess which engine produced it
+/// Only reads a bounded prefix of the (decompressed) file, so it's cheap even for huge dumps
+#[tauri::command]
+pub fn inspect_db_file(file_path: String) -> Result<DbFileInfo, DdevError> {
+    let mut file = File::open(&file_path)
+        .map_err(|e| DdevError::IoError(format!("Failed to open database file: {}", e)))?;
+    let file_size = file
+        .metadata()
+        .map_err(|e| DdevError::IoError(format!("Failed to read database file: {}", e)))?
+        .len();
+
+    let mut magic = [0u8; 4];
+    let magic_len = file
+        .read(&mut magic)
+        .map_err(|e| DdevError::IoError(format!("Failed to read database file: {}", e)))?;
+    file.seek(SeekFrom::Start(0))
+        .map_err(|e| DdevError::IoError(format!("Failed to read database file: {}", e)))?;
+
+    let is_gzip = magic_len >= 2 && magic[..2] == [0x1f, 0x8b];
+    let is_zip = magic_len >= 4 && magic == [b'P', b'K', 0x03, 0x04];
+
+    if is_zip {
+        // Zip archives need a full central directory read; don't guess
+        return Ok(DbFileInfo {
+            compressed: true,
+            likely_engine: None,
+            approx_uncompressed_size: None,
+        });
+    }
+
+    let mut prefix = Vec::new();
+    let approx_uncompressed_size = if is_gzip {
+        // The gzip trailer stores the uncompressed size modulo 2^32
+        let mut trailer = [0u8; 4];
+        let size = if file_size >= 4 {
+            file.seek(SeekFrom::End(-4))
+                .and_then(|_| file.read_exact(&mut trailer))
+                .and_then(|_| file.seek(SeekFrom::Start(0)))
+                .ok()
+                .map(|_| u32::from_le_bytes(trailer) as u64)
+        } else {
+            None
+        };
+
+        let decoder = flate2::read::MultiGzDecoder::new(&mut file);
+        // A truncated or oddly-compressed stream still yields a usable prefix
+        let _ = decoder.take(DB_FILE_PEEK_BYTES).read_to_end(&mut prefix);
+        size
+    } else {
+        (&mut file)
+            .take(DB_FILE_PEEK_BYTES)
+            .read_to_end(&mut prefix)
+            .map_err(|e| DdevError::IoError(format!("Failed to read database file: {}", e)))?;
+        Some(file_size)
+    };
+
+    Ok(DbFileInfo {
+        compressed: is_gzip,
+        likely_engine: guess_sql_engine(&String::from_utf8_lossy(&prefix)),
+        approx_uncompressed_size,
+    })
+}
+
+/// Guess the engine from dump headers and dialect-specific statements
+fn guess_sql_engine(sql: &str) -> Option<String> {
+    let postgres_hints = [
+        "PostgreSQL database dump",
+        "SELECT pg_catalog.set_config",
+        "SET statement_timeout",
+        "SET standard_conforming_strings",
+        "\nCOPY ",
+        "CREATE EXTENSION",
+    ];
+    let mysql_hints = [
+        "MySQL dump",
+        "/*!40101 SET",
+        "/*!40014 SET",
+        "ENGINE=InnoDB",
+        "LOCK TABLES `",
+        "CREATE TABLE `",
+    ];
+
+    if sql.contains("MariaDB dump") || sql.contains("-MariaDB") {
+        return Some("mariadb".to_string());
+    }
+
+    let postgres_score = postgres_hints.iter().filter(|h| sql.contains(*h)).count();
+    let mysql_score = mysql_hints.iter().filter(|h| sql.contains(*h)).count();
+
+    match postgres_score.cmp(&mysql_score) {
+        std::cmp::Ordering::Greater => Some("postgres".to_string()),
+        std::cmp::Ordering::Less => Some("mysql".to_string()),
+        std::cmp::Ordering::Equal => None,
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread;
+use tauri::{Emitter, Window};
+
+use crate::ddev::{
+    run_ddev_command_async, run_ddev_command_streaming, run_ddev_json_command_async,
+};
+use crate::error::DdevError;
+use crate::process::{
+    create_task_entry, generate_process_id, is_process_cancelled, remove_task_entry,
+};
+use crate::types::{CommandOutput, CommandStatus, DdevCacheEntry};
+
+/// Boolean settings in DDEV's global config that the app is allowed to change
+/// Each key maps to the `ddev config global --<key-with-dashes>` flag
+const GLOBAL_BOOLEAN_FLAGS: &[&str] = &[
+    "use_hardened_images",
+    "no_bind_mounts",
+    "use_letsencrypt",
+    "simple_formatting",
+    "fail_on_hook_fail",
+    "disable_http2",
+    "instrumentation_opt_in",
+];
+
+/// Router implementations DDEV's global `router` setting accepts
+const ROUTER_TYPES: &[&str] = &["traefik", "nginx-proxy"];
+
+/// Router DDEV uses when the global config doesn't set one
+const DEFAULT_ROUTER_TYPE: &str = "traefik";
+
+/// Directories in `~/.ddev` that only hold caches DDEV re-creates on demand
+/// (downloaded helper binaries, test cache, mutagen's data directory)
+const CLEARABLE_CACHE_DIRS: &[&str] = &["bin", "testcache", ".mdd", ".downloads"];
+
+/// Get DDEV's global directory (`~/.ddev`)
+fn get_global_ddev_dir() -> Result<PathBuf, DdevError> {
+    dirs::home_dir()
+        .map(|home| home.join(".ddev"))
+        .ok_or_else(|| DdevError::IoError("Could not determine home directory".to_string()))
+}
+
+/// Read DDEV's global config as raw JSON (`ddev config global --json-output`)
+pub async fn get_global_config() -> Result<serde_json::Value, DdevError> {
+    run_ddev_json_command_async(&["config", "global"]).await
+}
+
+/// Set a single DDEV global config flag (`ddev config global --<flag>=<value>`)
+pub async fn set_global_config_value(key: &str, value: &str) -> Result<(), DdevError> {
+    let flag = format!("--{}={}", key.replace('_', "-"), value);
+    run_ddev_command_async(&["config", "global", &flag]).await?;
+    Ok(())
+}
+
+/// Get the known boolean flags from DDEV's global config
+/// Flags missing from the config are reported as false (DDEV's default)
+#[tauri::command]
+pub async fn get_global_flags() -> Result<HashMap<String, bool>, DdevError> {
+    let config = get_global_config().await?;
+
+    Ok(GLOBAL_BOOLEAN_FLAGS
+        .iter()
+        .map(|key| {
+            let enabled = config.get(*key).and_then(|v| v.as_bool()).unwrap_or(false);
+            (key.to_string(), enabled)
+        })
+        .collect())
+}
+
+/// Set one of the known boolean flags in DDEV's global config
+#[tauri::command]
+pub async fn set_global_flag(key: String, value: bool) -> Result<(), DdevError> {
+    if !GLOBAL_BOOLEAN_FLAGS.contains(&key.as_str()) {
+        return Err(DdevError::CommandFailed(format!(
+            "Unsupported global config flag: {}",
+            key
+        )));
+    }
+
+    set_global_config_value(&key, &value.to_string()).await
+}
+
+/// Whether DDEV's anonymous usage telemetry (`instrumentation_opt_in`) is on
+#[tauri::command]
+pub async fn get_telemetry_status() -> Result<bool, DdevError> {
+    let config = get_global_config().await?;
+    Ok(config
+        .get("instrumentation_opt_in")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false))
+}
+
+/// Opt in to or out of DDEV's anonymous usage telemetry
+#[tauri::command]
+pub async fn set_telemetry(enabled: bool) -> Result<(), DdevError> {
+    set_global_config_value("instrumentation_opt_in", &enabled.to_string()).await
+}
+
+/// Get the router implementation DDEV uses for all projects (global `router` setting)
+#[tauri::command]
+pub async fn get_router_type() -> Result<String, DdevError> {
+    let config = get_global_config().await?;
+    Ok(config
+        .get("router")
+        .and_then(|v| v.as_str())
+        .filter(|router| !router.is_empty())
+        .unwrap_or(DEFAULT_ROUTER_TYPE)
+        .to_string())
+}
+
+/// Switch the router implementation for all projects (`ddev config global --router=<type>`)
+/// This affects every project, so `confirm` must be true (the UI warns the user first); a
+/// warning line is emitted as the change starts. Running projects pick it up after a restart.
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn set_router_type(
+    window: Window,
+    router_type: String,
+    confirm: bool,
+) -> Result<String, DdevError> {
+    if !ROUTER_TYPES.contains(&router_type.as_str()) {
+        return Err(DdevError::CommandFailed(format!(
+            "Unknown router type: {} (expected one of {})",
+            router_type,
+            ROUTER_TYPES.join(", ")
+        )));
+    }
+    if !confirm {
+        return Err(DdevError::CommandFailed(
+            "Changing the router affects all projects and must be confirmed".to_string(),
+        ));
+    }
+
+    // Warn before starting, so the warning precedes the command's output
+    let _ = window.emit(
+        "command-output",
+        CommandOutput::new(
+            format!(
+                "WARNING: switching the router to {} for all projects; restart running projects \
+                 (or power off DDEV) to apply it",
+                router_type
+            ),
+            "stderr",
+        ),
+    );
+
+    let router_flag = format!("--router={}", router_type);
+    run_ddev_command_streaming(
+        window,
+        "set-router",
+        "all",
+        &["config", "global", &router_flag],
+    )
+}
+
+/// Map registered project names to their approots by reading DDEV's files directly
+/// Unlike `ddev list` this works while Docker is down. Reads `~/.ddev/project_list.yaml`
+/// (DDEV 1.22+) and falls back to `project_info` in `~/.ddev/global_config.yaml` (older DDEV).
+#[tauri::command]
+pub fn get_registered_project_paths() -> Result<HashMap<String, String>, DdevError> {
+    let global_dir = get_global_ddev_dir()?;
+
+    let project_list = read_yaml(global_dir.join("project_list.yaml"))?;
+    if let Some(projects) = project_list.as_ref().and_then(|v| v.as_mapping()) {
+        return Ok(collect_project_paths(projects, "approotpath"));
+    }
+
+    let global_config = read_yaml(global_dir.join("global_config.yaml"))?;
+    Ok(global_config
+        .as_ref()
+        .and_then(|config| config.get("project_info"))
+        .and_then(|info| info.as_mapping())
+        .map(|projects| collect_project_paths(projects, "approot"))
+        .unwrap_or_default())
+}
+
+/// Read a YAML file, returning None if it doesn't exist
+fn read_yaml(path: PathBuf) -> Result<Option<serde_yaml::Value>, DdevError> {
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(DdevError::IoError(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+
+    serde_yaml::from_str(&content)
+        .map(Some)
+        .map_err(|e| DdevError::ParseError(format!("Failed to parse {}: {}", path.display(), e)))
+}
+
+/// Extract `name -> approot` pairs from a YAML mapping of projects
+fn collect_project_paths(
+    projects: &serde_yaml::Mapping,
+    approot_key: &str,
+) -> HashMap<String, String> {
+    projects
+        .iter()
+        .filter_map(|(name, info)| {
+            let name = name.as_str()?;
+            let approot = info.get(approot_key)?.as_str()?;
+            Some((name.to_string(), approot.to_string()))
+        })
+        .collect()
+}
+
+/// Total size of a file or directory tree in bytes (symlinks are not followed)
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Summarize the disk usage of each entry in `~/.ddev`, flagging which can be cleared
+#[tauri::command]
+pub async fn get_ddev_cache_usage() -> Result<Vec<DdevCacheEntry>, DdevError> {
+    let global_dir = get_global_ddev_dir()?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let entries = std::fs::read_dir(&global_dir)
+            .map_err(|e| DdevError::IoError(format!("Failed to read ~/.ddev: {}", e)))?;
+
+        let mut usage: Vec<DdevCacheEntry> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                DdevCacheEntry {
+                    bytes: disk_usage(&entry.path()),
+                    clearable: CLEARABLE_CACHE_DIRS.contains(&name.as_str()),
+                    name,
+                }
+            })
+            .collect();
+        usage.sort_by_key(|entry| std::cmp::Reverse(entry.bytes));
+        Ok(usage)
+    })
+    .await
+    .map_err(|e| DdevError::IoError(e.to_string()))?
+}
+
+/// Remove the given cache directories from `~/.ddev` (streaming what is removed)
+/// Only names in `CLEARABLE_CACHE_DIRS` are accepted; config files such as
+/// global_config.yaml and project_list.yaml can never be targeted
+/// Returns a process ID that can be used to cancel between targets
+#[tauri::command]
+pub fn clear_ddev_cache(window: Window, targets: Vec<String>) -> Result<String, DdevError> {
+    if targets.is_empty() {
+        return Err(DdevError::CommandFailed(
+            "No cache directories selected".to_string(),
+        ));
+    }
+    if let Some(target) = targets
+        .iter()
+        .find(|t| !CLEARABLE_CACHE_DIRS.contains(&t.as_str()))
+    {
+        return Err(DdevError::CommandFailed(format!(
+            "Refusing to remove {}: only {} can be cleared",
+            target,
+            CLEARABLE_CACHE_DIRS.join(", ")
+        )));
+    }
+
+    let global_dir = get_global_ddev_dir()?;
+    let process_id = generate_process_id();
+    let command_name = "clear-cache".to_string();
+    let process_id_clone = process_id.clone();
+
+    create_task_entry(&process_id, &command_name, "all");
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: "all".to_string(),
+            status: "started".to_string(),
+            message: Some(format!("Clearing {}", targets.join(", "))),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let emit_line = |line: String, stream: &str| {
+            let _ = window.emit("command-output", CommandOutput::new(line, stream));
+        };
+
+        let mut failed = Vec::new();
+        for target in &targets {
+            if is_process_cancelled(&process_id_clone) {
+                return;
+            }
+
+            let path = global_dir.join(target);
+            if !path.exists() {
+                emit_line(
+                    format!("Skipping ~/.ddev/{} (not present)", target),
+                    "stdout",
+                );
+                continue;
+            }
+
+            let bytes = disk_usage(&path);
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            match result {
+                Ok(()) => emit_line(
+                    format!("Removed ~/.ddev/{} ({} bytes)", target, bytes),
+                    "stdout",
+                ),
+                Err(e) => {
+                    emit_line(
+                        format!("Failed to remove ~/.ddev/{}: {}", target, e),
+                        "stderr",
+                    );
+                    failed.push(target.clone());
+                }
+            }
+        }
+
+        remove_task_entry(&process_id_clone);
+        let (status, message) = if failed.is_empty() {
+            ("finished", "DDEV cache cleared".to_string())
+        } else {
+            ("error", format!("Failed to clear {}", failed.join(", ")))
+        };
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project: "all".to_string(),
+                status: status.to_string(),
+                message: Some(message),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
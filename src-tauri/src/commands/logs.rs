@@ -1,14 +1,193 @@
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use tauri::{Emitter, Window};
+use tokio::process::Command as AsyncCommand;
 
-use crate::ddev::{get_ddev_command, get_enhanced_path};
+use crate::ddev::{
+    get_ddev_base_args, get_ddev_command, get_enhanced_path, run_ddev_command_in_dir_async,
+    run_ddev_json_command_async,
+};
 use crate::error::DdevError;
-use crate::process::{generate_process_id, ProcessEntry, PROCESS_REGISTRY};
-use crate::types::{LogOutput, LogStatus};
+use crate::process::{generate_process_id, register_child_process, ProcessEntry, PROCESS_REGISTRY};
+use crate::types::{AccessLogEntry, DdevProjectDetails, LogOutput, LogStatus, SlowQuery};
+
+/// Lines kept per paused log stream; older lines are dropped once it's full
+const PAUSED_LOG_BUFFER: usize = 500;
+
+/// Pause state of a running log stream
+#[derive(Default)]
+struct LogStreamState {
+    paused: AtomicBool,
+    // Lines received while paused, emitted on resume
+    buffered: Mutex<VecDeque<LogOutput>>,
+}
+
+impl LogStreamState {
+    /// Emit a line, or buffer it while the stream is paused
+    fn emit(&self, window: &Window, output: LogOutput) {
+        {
+            let mut buffered = self.buffered.lock().unwrap();
+            if self.paused.load(Ordering::SeqCst) {
+                if buffered.len() == PAUSED_LOG_BUFFER {
+                    buffered.pop_front();
+                }
+                buffered.push_back(output);
+                return;
+            }
+        }
+        let _ = window.emit("log-output", output);
+    }
+}
+
+// Pause state per active log stream, keyed by process ID
+static LOG_STREAMS: Lazy<Mutex<HashMap<String, Arc<LogStreamState>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Stop emitting a log stream's lines (e.g. while the log panel is hidden) without stopping
+/// `ddev logs`, so no lines are missed; the most recent lines are kept for `resume_log_stream`
+/// Returns false if there's no such stream
+#[tauri::command]
+pub fn pause_log_stream(process_id: String) -> bool {
+    let streams = LOG_STREAMS.lock().unwrap();
+    match streams.get(&process_id) {
+        Some(state) => {
+            state.paused.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Resume a paused log stream, first emitting the lines buffered while it was paused
+/// Returns false if there's no such stream
+#[tauri::command]
+pub fn resume_log_stream(window: Window, process_id: String) -> bool {
+    let Some(state) = LOG_STREAMS.lock().unwrap().get(&process_id).cloned() else {
+        return false;
+    };
+
+    // Hold the buffer lock so reader threads can't emit newer lines before the backlog
+    let mut buffered = state.buffered.lock().unwrap();
+    state.paused.store(false, Ordering::SeqCst);
+    for output in buffered.drain(..) {
+        let _ = window.emit("log-output", output);
+    }
+    true
+}
+
+/// Watches a db log stream for slow queries, emitting `slow-query` for those over a threshold
+/// MariaDB/MySQL log a "# Query_time: 2.000312  Lock_time: ..." header followed by the
+/// statement on a later line; Postgres logs "LOG:  duration: 2000.312 ms  statement: ..."
+#[derive(Clone)]
+struct SlowQueryWatcher {
+    threshold: f64,
+    // Lines start with a timestamp (`ddev logs -t`)
+    timestamps: bool,
+    // Query time from a MariaDB header, waiting for its statement
+    pending: Option<f64>,
+}
+
+impl SlowQueryWatcher {
+    fn new(threshold: f64, timestamps: bool) -> Self {
+        Self {
+            threshold,
+            timestamps,
+            pending: None,
+        }
+    }
+
+    fn check(&mut self, window: &Window, line: &str) {
+        let line = match line.split_once(' ') {
+            Some((_, rest)) if self.timestamps => rest,
+            _ => line,
+        };
+        if let Some(slow_query) = self.parse(line.trim()) {
+            if slow_query.seconds >= self.threshold {
+                let _ = window.emit("slow-query", slow_query);
+            }
+        }
+    }
+
+    fn parse(&mut self, line: &str) -> Option<SlowQuery> {
+        if let Some((_, rest)) = line.split_once("# Query_time:") {
+            self.pending = rest.split_whitespace().next().and_then(|t| t.parse().ok());
+            return None;
+        }
+
+        if let Some((_, rest)) = line.split_once("duration: ") {
+            let (millis, statement) = rest.split_once(" ms")?;
+            // "statement: ..." for simple queries, "execute <name>: ..." for prepared ones
+            let query = statement.split_once(": ").map_or(statement, |(_, q)| q);
+            return Some(SlowQuery {
+                seconds: millis.trim().parse::<f64>().ok()? / 1000.0,
+                query: query.trim().to_string(),
+            });
+        }
+
+        // Between a MariaDB header and its statement come more "#" lines and session setup
+        let seconds = self.pending?;
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("SET timestamp=")
+            || line.starts_with("use ")
+        {
+            return None;
+        }
+        self.pending = None;
+        Some(SlowQuery {
+            seconds,
+            query: line.to_string(),
+        })
+    }
+}
+
+/// Turn on a project's slow query log for queries taking `threshold` seconds or longer,
+/// logging to the db container's output so `ddev logs -s db` includes it
+/// The setting lasts until the database server restarts.
+async fn enable_slow_query_log(project: &str, threshold: f64) -> Result<(), DdevError> {
+    let details: DdevProjectDetails = run_ddev_json_command_async(&["describe", project]).await?;
+    let database_type = details
+        .dbinfo
+        .map(|info| info.database_type)
+        .or(details.database_type)
+        .unwrap_or_else(|| "mariadb".to_string());
+
+    if database_type == "postgres" {
+        // ALTER SYSTEM can't run inside a transaction, so each statement gets its own -c
+        let set_duration = format!(
+            "ALTER SYSTEM SET log_min_duration_statement = {}",
+            (threshold * 1000.0).round() as u64
+        );
+        run_ddev_command_in_dir_async(
+            &["psql", "-c", &set_duration, "-c", "SELECT pg_reload_conf()"],
+            &details.approot,
+        )
+        .await?;
+    } else {
+        // Setting globals needs root; DDEV's root password is "root"
+        let statements = format!(
+            "SET GLOBAL slow_query_log_file = '/dev/stderr'; \
+             SET GLOBAL long_query_time = {}; SET GLOBAL slow_query_log = 1;",
+            threshold
+        );
+        run_ddev_command_in_dir_async(
+            &["mysql", "-uroot", "-proot", "-e", &statements],
+            &details.approot,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
 
 /// Get logs from a DDEV project container (streaming)
+/// With `slow_query_threshold` (seconds) and the `db` service, also turns on the slow query
+/// log and emits `slow-query` events for queries at least that slow, alongside the raw lines
 /// Returns a process ID that can be used to cancel/stop the log stream
 #[tauri::command]
 pub fn get_logs(
@@ -18,6 +197,7 @@ pub fn get_logs(
     follow: bool,
     tail: Option<u32>,
     timestamps: bool,
+    slow_query_threshold: Option<f64>,
 ) -> Result<String, DdevError> {
     let process_id = generate_process_id();
     let ddev_cmd = get_ddev_command();
@@ -55,8 +235,28 @@ pub fn get_logs(
         },
     );
 
+    let slow_query_threshold = slow_query_threshold.filter(|_| service == "db");
+
     // Spawn the command in a background thread
     thread::spawn(move || {
+        if let Some(threshold) = slow_query_threshold {
+            let enabled =
+                tauri::async_runtime::block_on(enable_slow_query_log(&project_clone, threshold));
+            if let Err(e) = enabled {
+                let _ = window.emit(
+                    "log-output",
+                    LogOutput {
+                        line: format!("Could not enable the slow query log: {}", e),
+                        stream: "stderr".to_string(),
+                        project: project_clone.clone(),
+                        service: service_clone.clone(),
+                    },
+                );
+            }
+        }
+        let slow_queries =
+            slow_query_threshold.map(|threshold| SlowQueryWatcher::new(threshold, timestamps));
+
         let result = Command::new(&ddev_cmd)
             .args(&args)
             .env("PATH", &enhanced_path)
@@ -93,11 +293,20 @@ pub fn get_logs(
                     child: Some(child),
                     command: "logs".to_string(),
                     project: project_clone.clone(),
+                    stdin: None,
                 },
             );
         }
 
+        let stream_state = Arc::new(LogStreamState::default());
+        LOG_STREAMS
+            .lock()
+            .unwrap()
+            .insert(process_id_clone.clone(), stream_state.clone());
+
         let window_clone = window.clone();
+        let stdout_state = stream_state.clone();
+        let stderr_state = stream_state;
         let project_for_stdout = project_clone.clone();
         let service_for_stdout = service_clone.clone();
         let project_for_stderr = project_clone.clone();
@@ -106,11 +315,15 @@ pub fn get_logs(
         // Spawn thread for stdout
         let stdout_handle = stdout.map(|stdout| {
             let window = window.clone();
+            let mut slow_queries = slow_queries.clone();
             thread::spawn(move || {
                 let reader = BufReader::new(stdout);
                 for line in reader.lines().map_while(Result::ok) {
-                    let _ = window.emit(
-                        "log-output",
+                    if let Some(watcher) = slow_queries.as_mut() {
+                        watcher.check(&window, &line);
+                    }
+                    stdout_state.emit(
+                        &window,
                         LogOutput {
                             line,
                             stream: "stdout".to_string(),
@@ -124,11 +337,15 @@ pub fn get_logs(
 
         // Spawn thread for stderr
         let stderr_handle = stderr.map(|stderr| {
+            let mut slow_queries = slow_queries;
             thread::spawn(move || {
                 let reader = BufReader::new(stderr);
                 for line in reader.lines().map_while(Result::ok) {
-                    let _ = window_clone.emit(
-                        "log-output",
+                    if let Some(watcher) = slow_queries.as_mut() {
+                        watcher.check(&window_clone, &line);
+                    }
+                    stderr_state.emit(
+                        &window_clone,
                         LogOutput {
                             line,
                             stream: "stderr".to_string(),
@@ -147,6 +364,7 @@ pub fn get_logs(
         if let Some(handle) = stderr_handle {
             let _ = handle.join();
         }
+        LOG_STREAMS.lock().unwrap().remove(&process_id_clone);
 
         // Retrieve child from registry and wait for completion
         let status = {
@@ -189,3 +407,308 @@ pub fn get_logs(
 
     Ok(process_id)
 }
+
+/// Level names (lowercased) for each severity, most severe first
+/// Matched only as level tokens (see `infer_log_level`), never as plain substrings, so request
+/// paths like `/error-page` or `/alerts` in access log lines don't count
+const LOG_LEVEL_NAMES: &[(&str, &[&str])] = &[
+    (
+        "error",
+        &[
+            "error", "err", "fatal", "panic", "crit", "critical", "alert", "emerg",
+        ],
+    ),
+    ("warning", &["warn", "warning"]),
+];
+
+/// PHP's message prefixes for each severity (case-sensitive), in `LOG_LEVEL_NAMES` order
+const PHP_LEVEL_PREFIXES: &[(&str, &[&str])] = &[
+    (
+        "error",
+        &["Fatal error:", "Parse error:", "Recoverable fatal error:"],
+    ),
+    ("warning", &["Warning:", "Deprecated:"]),
+];
+
+/// Infer a log line's level ("error", "warning" or "info") from common log formats:
+/// bracketed levels (nginx `[error]`, MySQL `[ERROR]`, apache `[php:error]`), a level word
+/// followed by a colon (php-fpm/Postgres `FATAL:`), `level=error`, and PHP's `Fatal error:`
+fn infer_log_level(line: &str) -> &'static str {
+    let mut levels: Vec<String> = Vec::new();
+    for token in line.split_whitespace() {
+        // `[error]`, `[php:error]`, `[Warning]`
+        if let Some(inner) = token.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            levels.push(inner.rsplit(':').next().unwrap_or(inner).to_lowercase());
+        }
+        // `FATAL:`, `ERROR:`
+        if let Some(word) = token.strip_suffix(':') {
+            levels.push(word.to_lowercase());
+        }
+        // `level=error`
+        if let Some((key, value)) = token.split_once('=') {
+            if key.eq_ignore_ascii_case("level") {
+                levels.push(value.trim_matches('"').to_lowercase());
+            }
+        }
+    }
+
+    LOG_LEVEL_NAMES
+        .iter()
+        .zip(PHP_LEVEL_PREFIXES)
+        .find(|((_, names), (_, prefixes))| {
+            levels.iter().any(|level| names.contains(&level.as_str()))
+                || prefixes.iter().any(|prefix| line.contains(prefix))
+        })
+        .map(|((level, _), _)| *level)
+        .unwrap_or("info")
+}
+
+/// Get the error lines among a service's last `lines` log lines (one-shot, no streaming)
+/// Returns an empty list for a stopped project or a service without logs
+#[tauri::command]
+pub async fn get_recent_errors(
+    project: String,
+    service: String,
+    lines: u32,
+) -> Result<Vec<String>, DdevError> {
+    let tail = format!("--tail={}", lines.max(1));
+    let mut args = get_ddev_base_args();
+    args.extend_from_slice(&["logs", "-s", &service, &tail, &project]);
+
+    let output = AsyncCommand::new(get_ddev_command())
+        .args(&args)
+        .env("PATH", get_enhanced_path())
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                DdevError::NotInstalled
+            } else {
+                DdevError::IoError(format!("Failed to get logs: {}", e))
+            }
+        })?;
+
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    // Containers log to both streams (e.g. nginx/php-fpm errors go to stderr)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(stdout
+        .lines()
+        .chain(stderr.lines())
+        .filter(|line| infer_log_level(line) == "error")
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// ddev error lines kept to explain why an access log stream ended
+const ACCESS_LOG_ERROR_LINES: usize = 5;
+
+/// Access logs inside the web container (nginx-fpm and apache-fpm webservers)
+const WEB_ACCESS_LOGS: &[&str] = &["/var/log/nginx/access.log", "/var/log/apache2/access.log"];
+
+/// Parse a common/combined log format line into (method, path, status, ms)
+/// e.g. `1.2.3.4 - - [date] "GET /path HTTP/1.1" 200 512 "ref" "agent" 0.012`; the duration
+/// is only present when the log format appends the request time in seconds
+fn parse_access_log_line(line: &str) -> Option<(String, String, u16, Option<u64>)> {
+    let (_, after_quote) = line.split_once('"')?;
+    let (request, rest) = after_quote.split_once('"')?;
+
+    let mut request_parts = request.split_whitespace();
+    let method = request_parts.next()?.to_string();
+    let path = request_parts.next()?.to_string();
+    let status = rest.split_whitespace().next()?.parse::<u16>().ok()?;
+
+    let ms = line
+        .rsplit_once(' ')
+        .map(|(_, last)| last)
+        .filter(|last| last.contains('.'))
+        .and_then(|last| last.parse::<f64>().ok())
+        .map(|secs| (secs * 1000.0).round() as u64);
+
+    Some((method, path, status, ms))
+}
+
+/// Stream a project's web server access log as structured `access-log` events
+/// Tails the log inside the project's own web container, which the router only sends this
+/// project's traffic to. The log format has no Host field, so requests aren't filtered further
+/// by hostname: all of the project's hostnames are included. Returns a process ID; cancel it
+/// with `cancel_command` to stop the stream.
+#[tauri::command]
+pub fn stream_access_log(
+    window: Window,
+    project: String,
+    approot: String,
+) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let service = "web".to_string();
+
+    let mut args = get_ddev_base_args();
+    // -F keeps following if a log doesn't exist yet or is rotated; -q drops file headers
+    args.extend_from_slice(&["exec", "tail", "-q", "-n", "0", "-F"]);
+    args.extend_from_slice(WEB_ACCESS_LOGS);
+
+    let mut child = Command::new(get_ddev_command())
+        .args(&args)
+        .current_dir(&approot)
+        .env("PATH", get_enhanced_path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| DdevError::IoError(format!("Failed to tail access log: {}", e)))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    register_child_process(&process_id, child, "access-log", &project);
+
+    let _ = window.emit(
+        "log-status",
+        LogStatus {
+            project: project.clone(),
+            service: service.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Watching requests for {}", project)),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    let process_id_clone = process_id.clone();
+    thread::spawn(move || {
+        // Keep ddev's last few error lines to explain why the stream ended (e.g. the
+        // project isn't running); tail's own complaints are about whichever of the
+        // webservers' logs doesn't exist, so they're skipped
+        let stderr_handle = stderr.map(|stderr| {
+            thread::spawn(move || {
+                let mut errors: VecDeque<String> = VecDeque::new();
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let line = line.trim().to_string();
+                    if line.is_empty() || line.starts_with("tail:") {
+                        continue;
+                    }
+                    if errors.len() == ACCESS_LOG_ERROR_LINES {
+                        errors.pop_front();
+                    }
+                    errors.push_back(line);
+                }
+                Vec::from(errors).join("\n")
+            })
+        });
+
+        if let Some(stdout) = stdout {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let parsed = parse_access_log_line(&line);
+                let _ = window.emit(
+                    "access-log",
+                    AccessLogEntry {
+                        project: project.clone(),
+                        method: parsed.as_ref().map(|(method, ..)| method.clone()),
+                        path: parsed.as_ref().map(|(_, path, ..)| path.clone()),
+                        status: parsed.as_ref().map(|(_, _, status, _)| *status),
+                        ms: parsed.as_ref().and_then(|(.., ms)| *ms),
+                        raw: line,
+                    },
+                );
+            }
+        }
+
+        let errors = stderr_handle
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+
+        let status = {
+            let mut registry = PROCESS_REGISTRY.lock().unwrap();
+            registry
+                .remove(&process_id_clone)
+                .and_then(|entry| entry.child.map(|mut child| child.wait()))
+        };
+
+        // None means it was cancelled - cancel_command handles the status
+        if status.is_some() {
+            let message = if errors.is_empty() {
+                "Access log stream ended (is the project running?)".to_string()
+            } else {
+                format!("Access log stream ended: {}", errors)
+            };
+            let _ = window.emit(
+                "log-status",
+                LogStatus {
+                    project,
+                    service,
+                    status: "error".to_string(),
+                    message: Some(message),
+                    process_id: None,
+                },
+            );
+        }
+    });
+
+    Ok(process_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_level_from_level_tokens() {
+        assert_eq!(
+            infer_log_level(
+                "2024/01/01 12:00:00 [error] 12#12: *3 FastCGI sent in stderr: \"Primary script unknown\""
+            ),
+            "error"
+        );
+        assert_eq!(
+            infer_log_level(
+                "PHP Fatal error:  Uncaught Exception: boom in /var/www/html/index.php:3"
+            ),
+            "error"
+        );
+        assert_eq!(
+            infer_log_level("2024-01-01T12:00:00.000000Z 0 [ERROR] [MY-010119] [Server] Aborting"),
+            "error"
+        );
+        assert_eq!(
+            infer_log_level("2024-01-01 12:00:00 UTC [42] FATAL:  role \"x\" does not exist"),
+            "error"
+        );
+        assert_eq!(
+            infer_log_level("[Mon Jan 01 12:00:00 2024] [php:error] [pid 12] PHP Parse error"),
+            "error"
+        );
+        assert_eq!(infer_log_level("time=now level=error msg=failed"), "error");
+        assert_eq!(
+            infer_log_level(
+                "[01-Jan-2024 12:00:00] WARNING: [pool www] server reached pm.max_children"
+            ),
+            "warning"
+        );
+        assert_eq!(
+            infer_log_level("PHP Deprecated:  Function each() is deprecated"),
+            "warning"
+        );
+    }
+
+    #[test]
+    fn log_level_ignores_access_log_paths() {
+        assert_eq!(
+            infer_log_level(
+                "172.18.0.2 - - [01/Jan/2024:12:00:00 +0000] \"GET /error-page HTTP/1.1\" 200 512 \"-\" \"curl\""
+            ),
+            "info"
+        );
+        assert_eq!(
+            infer_log_level(
+                "172.18.0.2 - - [01/Jan/2024:12:00:00 +0000] \"GET /alerts?warn=1 HTTP/1.1\" 200 64 \"-\" \"curl\""
+            ),
+            "info"
+        );
+        assert_eq!(
+            infer_log_level(
+                "172.18.0.2 - - [01/Jan/2024:12:00:00 +0000] \"POST /api/exception/fatal HTTP/1.1\" 201 0 \"-\" \"curl\""
+            ),
+            "info"
+        );
+        assert_eq!(infer_log_level("Starting nginx: nginx."), "info");
+    }
+}
@@ -1,19 +1,29 @@
 mod addons;
 mod create;
 mod database;
+mod global_config;
 mod logs;
 mod projects;
+mod providers;
 mod schema;
 mod screenshots;
+mod share;
 mod snapshots;
+mod templates;
 mod utils;
+mod window;
 
 pub use addons::*;
 pub use create::*;
 pub use database::*;
+pub use global_config::*;
 pub use logs::*;
 pub use projects::*;
+pub use providers::*;
 pub use schema::*;
 pub use screenshots::*;
+pub use share::*;
 pub use snapshots::*;
+pub use templates::*;
 pub use utils::*;
+pub use window::*;
@@ -1,16 +1,38 @@
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{Emitter, Window};
 use tokio::process::Command as AsyncCommand;
 
+use crate::commands::{check_ca_installed, get_registered_project_paths};
 use crate::ddev::{
-    get_ddev_base_args, get_ddev_command, get_enhanced_path, run_ddev_command_streaming,
-    run_ddev_json_command_async, run_streaming_command,
+    get_ddev_base_args, get_ddev_command, get_enhanced_path, run_ddev_command_streaming_in_dir,
+    run_ddev_command_streaming_with_callback, run_ddev_json_command_async,
+    run_ddev_json_command_in_dir_async, run_docker_command_async, run_streaming_command,
+    run_streaming_command_with_outcome,
 };
 use crate::error::DdevError;
+use crate::fs_utils::write_atomic;
+use crate::http::http_client;
 use crate::process::{
-    create_task_entry, generate_process_id, is_process_cancelled, remove_task_entry,
+    create_task_entry, generate_process_id, is_command_running, is_process_cancelled,
+    register_child_process, remove_task_entry, wait_for_registered_child,
+};
+use crate::pty::run_pty_command;
+use crate::settings::{
+    load_settings, record_start_result, record_start_time, remove_project_note, update_settings,
+    StartResult,
+};
+use crate::types::{
+    CommandOutput, CommandStatus, ComposerScript, ConfigBackup, DdevProjectBasic,
+    DdevProjectDetails, DeletePreview, HostnameConflict, ImageInfo, ImagesPresent, PortCheck,
+    PortClaim, PortConflict, PoweroffComplete, ProjectFilter, ProjectListRefresh, PublishedPort,
+    ServiceStatus, TaskPhase, TimeSkew, XdebugState,
 };
-use crate::types::{CommandOutput, CommandStatus, DdevProjectBasic, DdevProjectDetails};
 
 /// List all DDEV projects
 #[tauri::command]
@@ -18,10 +40,539 @@ pub async fn list_projects() -> Result<Vec<DdevProjectBasic>, DdevError> {
     run_ddev_json_command_async(&["list"]).await
 }
 
+/// Get one project's `ddev list` entry, or None if no project has that name
+/// Lighter than `describe_project` when only status/URLs are needed (e.g. tray, badges)
+#[tauri::command]
+pub async fn get_project_basic(name: String) -> Result<Option<DdevProjectBasic>, DdevError> {
+    let projects: Vec<DdevProjectBasic> = run_ddev_json_command_async(&["list"]).await?;
+    Ok(projects.into_iter().find(|project| project.name == name))
+}
+
+/// List DDEV projects in the user's saved order
+/// Projects missing from the saved order (e.g. newly created) keep `ddev list` order and
+/// are appended; saved names that no longer exist are ignored
+#[tauri::command]
+pub async fn list_projects_ordered() -> Result<Vec<DdevProjectBasic>, DdevError> {
+    let mut projects: Vec<DdevProjectBasic> = run_ddev_json_command_async(&["list"]).await?;
+    let order = load_settings().project_order;
+
+    projects.sort_by_key(|p| {
+        order
+            .iter()
+            .position(|name| *name == p.name)
+            .unwrap_or(usize::MAX)
+    });
+    Ok(projects)
+}
+
+/// Get the saved project order (project names, first to last)
+#[tauri::command]
+pub fn get_project_order() -> Vec<String> {
+    load_settings().project_order
+}
+
+/// Save a manual project order, e.g. after drag-to-reorder in the project list
+#[tauri::command]
+pub fn set_project_order(names: Vec<String>) -> Result<(), DdevError> {
+    let mut order: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        if !order.contains(&name) {
+            order.push(name);
+        }
+    }
+
+    update_settings(|settings| settings.project_order = order)
+}
+
+/// List projects without Docker, for when `list_projects` fails with `DockerNotRunning`
+/// Names and approots come from DDEV's project registry, type and docroot from each
+/// project's `.ddev/config.yaml`; status is always "unknown"
+#[tauri::command]
+pub fn list_projects_offline() -> Result<Vec<DdevProjectBasic>, DdevError> {
+    let home = dirs::home_dir();
+
+    let mut projects: Vec<DdevProjectBasic> = get_registered_project_paths()?
+        .into_iter()
+        .map(|(name, approot)| {
+            let config: Option<serde_yaml::Value> =
+                std::fs::read_to_string(std::path::Path::new(&approot).join(".ddev/config.yaml"))
+                    .ok()
+                    .and_then(|content| serde_yaml::from_str(&content).ok());
+            let config_str = |key: &str| {
+                config
+                    .as_ref()
+                    .and_then(|c| c.get(key))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+
+            // `strip_prefix` compares whole path components, so `/home/u/site2` isn't
+            // treated as being under `/home/u/site`
+            let shortroot = match home
+                .as_ref()
+                .and_then(|home| std::path::Path::new(&approot).strip_prefix(home).ok())
+            {
+                Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+                Some(rest) => format!("~{}{}", std::path::MAIN_SEPARATOR, rest.display()),
+                None => approot.clone(),
+            };
+
+            DdevProjectBasic {
+                name,
+                status: "unknown".to_string(),
+                status_desc: "unknown".to_string(),
+                project_type: config_str("type"),
+                docroot: config_str("docroot"),
+                shortroot,
+                approot,
+                primary_url: String::new(),
+                httpurl: String::new(),
+                httpsurl: String::new(),
+                mailpit_url: String::new(),
+                mailpit_https_url: String::new(),
+                xhgui_url: String::new(),
+                xhgui_https_url: String::new(),
+                router: String::new(),
+                router_disabled: false,
+                mutagen_enabled: false,
+                nodejs_version: config_str("nodejs_version"),
+            }
+        })
+        .collect();
+
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(projects)
+}
+
+/// Default time allowed for `ddev list` in `refresh_projects`
+const LIST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// List projects with a timeout and errors the UI can act on
+/// Fails with `NotInstalled` or `Timeout` (Docker slow or hung); when Docker isn't running,
+/// returns the offline list with `partial: true` (or `DockerNotRunning` if that fails too)
+#[tauri::command]
+pub async fn refresh_projects(timeout_secs: Option<u64>) -> Result<ProjectListRefresh, DdevError> {
+    let timeout = timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(LIST_TIMEOUT);
+
+    match tokio::time::timeout(timeout, run_ddev_json_command_async(&["list"])).await {
+        Ok(Ok(projects)) => Ok(ProjectListRefresh {
+            projects,
+            partial: false,
+        }),
+        Ok(Err(DdevError::DockerNotRunning)) => list_projects_offline()
+            .map(|projects| ProjectListRefresh {
+                projects,
+                partial: true,
+            })
+            .map_err(|_| DdevError::DockerNotRunning),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(DdevError::Timeout(format!(
+            "ddev list did not finish within {}s",
+            timeout.as_secs()
+        ))),
+    }
+}
+
+/// DDEV's default top-level domain for project hostnames
+const DEFAULT_PROJECT_TLD: &str = "ddev.site";
+
+/// Collect the hostnames a project routes, from `.ddev/config.yaml` and `config.*.yaml` overrides
+/// (override lists are appended, as DDEV merges them)
+fn project_hostnames(name: &str, approot: &str) -> Vec<String> {
+    let ddev_dir = std::path::Path::new(approot).join(".ddev");
+    let mut config_files = vec![ddev_dir.join("config.yaml")];
+    if let Ok(entries) = std::fs::read_dir(&ddev_dir) {
+        let mut overrides: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name().map(|f| f.to_string_lossy()).is_some_and(|f| {
+                    f.starts_with("config.") && f.ends_with(".yaml") && f != "config.yaml"
+                })
+            })
+            .collect();
+        overrides.sort();
+        config_files.extend(overrides);
+    }
+
+    let mut tld = DEFAULT_PROJECT_TLD.to_string();
+    let mut additional_hostnames = Vec::new();
+    let mut fqdns = Vec::new();
+    for path in config_files {
+        let Some(config) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        else {
+            continue;
+        };
+        if let Some(project_tld) = config.get("project_tld").and_then(|v| v.as_str()) {
+            tld = project_tld.to_string();
+        }
+        let strings = |key: &str| -> Vec<String> {
+            config
+                .get(key)
+                .and_then(|v| v.as_sequence())
+                .map(|seq| {
+                    seq.iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_lowercase()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        additional_hostnames.extend(strings("additional_hostnames"));
+        fqdns.extend(strings("additional_fqdns"));
+    }
+
+    let mut hostnames = vec![format!("{}.{}", name.to_lowercase(), tld)];
+    hostnames.extend(
+        additional_hostnames
+            .into_iter()
+            .map(|h| format!("{}.{}", h, tld)),
+    );
+    hostnames.extend(fqdns);
+    hostnames.sort();
+    hostnames.dedup();
+    hostnames
+}
+
+/// Find hostnames claimed by more than one project (via `additional_hostnames`/`additional_fqdns`)
+/// Reads project config directly so it works whether or not projects are running
+#[tauri::command]
+pub fn detect_hostname_conflicts() -> Result<Vec<HostnameConflict>, DdevError> {
+    let mut claims: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, approot) in get_registered_project_paths()? {
+        for hostname in project_hostnames(&name, &approot) {
+            claims.entry(hostname).or_default().push(name.clone());
+        }
+    }
+
+    Ok(claims
+        .into_iter()
+        .filter(|(_, projects)| projects.len() > 1)
+        .map(|(hostname, mut projects)| {
+            projects.sort();
+            HostnameConflict { hostname, projects }
+        })
+        .collect())
+}
+
+/// List DDEV projects, filtered server-side to keep payloads small on large installs
+/// All filters are optional and case-insensitive; `name_contains` matches a substring
+#[tauri::command]
+pub async fn list_projects_filtered(
+    status: Option<String>,
+    type_filter: Option<String>,
+    name_contains: Option<String>,
+) -> Result<Vec<DdevProjectBasic>, DdevError> {
+    let projects: Vec<DdevProjectBasic> = run_ddev_json_command_async(&["list"]).await?;
+
+    let status = status.map(|s| s.to_lowercase());
+    let type_filter = type_filter.map(|t| t.to_lowercase());
+    let name_contains = name_contains.map(|n| n.to_lowercase());
+
+    Ok(projects
+        .into_iter()
+        .filter(|p| {
+            status
+                .as_ref()
+                .is_none_or(|s| p.status.to_lowercase() == *s)
+        })
+        .filter(|p| {
+            type_filter
+                .as_ref()
+                .is_none_or(|t| p.project_type.to_lowercase() == *t)
+        })
+        .filter(|p| {
+            name_contains
+                .as_ref()
+                .is_none_or(|n| p.name.to_lowercase().contains(n.as_str()))
+        })
+        .collect())
+}
+
+/// ddev subcommands that only touch the project they run in, so are safe in bulk runs
+/// Anything not listed here (delete, clean, poweroff, custom commands...) is rejected
+const BULK_SAFE_COMMANDS: &[&str] = &[
+    "start", "stop", "restart", "describe", "xdebug", "xhprof", "snapshot", "mutagen",
+];
+
+/// ddev subcommands that pass their arguments to a tool in the web container, so the
+/// arguments after them aren't ddev flags
+const BULK_PASSTHROUGH_COMMANDS: &[&str] = &[
+    "exec", "composer", "drush", "wp", "artisan", "craft", "magento", "npm", "yarn", "php",
+];
+
+/// ddev command aliases, resolved before checking the subcommand
+const BULK_COMMAND_ALIASES: &[(&str, &str)] = &[
+    ("rm", "stop"),
+    ("remove", "stop"),
+    ("status", "describe"),
+    ("st", "describe"),
+    ("desc", "describe"),
+    ("powerdown", "poweroff"),
+];
+
+/// Global ddev flags allowed before the subcommand
+const BULK_GLOBAL_FLAGS: &[&str] = &["--json-output", "-j", "--skip-hooks"];
+
+/// Flags that make a safe subcommand act on every project or remove project data, by
+/// subcommand ("" applies to all)
+const BULK_DISALLOWED_FLAGS: &[(&str, &[&str])] = &[
+    ("", &["--all", "-a"]),
+    ("stop", &["--remove-data", "-R", "--unlist", "-U"]),
+    ("snapshot", &["--cleanup", "-C"]),
+];
+
+/// Check that `command_args` is safe to run in every project, returning the (alias-resolved)
+/// subcommand. The subcommand is the first argument that isn't a flag
+fn check_bulk_command(command_args: &[String]) -> Result<String, DdevError> {
+    let rejected = |what: &str| {
+        Err(DdevError::CommandFailed(format!(
+            "ddev {} can't be run across projects",
+            what
+        )))
+    };
+
+    let Some(position) = command_args.iter().position(|a| !a.starts_with('-')) else {
+        return Err(DdevError::CommandFailed("No command given".to_string()));
+    };
+    if let Some(flag) = command_args[..position]
+        .iter()
+        .find(|a| !BULK_GLOBAL_FLAGS.contains(&flag_name(a)))
+    {
+        return rejected(flag);
+    }
+
+    let given = command_args[position].as_str();
+    let subcommand = BULK_COMMAND_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == given)
+        .map_or(given, |(_, command)| command);
+
+    if BULK_PASSTHROUGH_COMMANDS.contains(&subcommand) {
+        return Ok(subcommand.to_string());
+    }
+    if !BULK_SAFE_COMMANDS.contains(&subcommand) {
+        return rejected(subcommand);
+    }
+
+    let disallowed: Vec<&str> = BULK_DISALLOWED_FLAGS
+        .iter()
+        .filter(|(command, _)| command.is_empty() || *command == subcommand)
+        .flat_map(|(_, flags)| flags.iter().copied())
+        .collect();
+    for arg in &command_args[position + 1..] {
+        let name = flag_name(arg);
+        // Short flags can be bundled, e.g. `-aO`
+        let bundled = !name.starts_with("--") && name.starts_with('-') && name.len() > 2;
+        let hit = if bundled {
+            name[1..]
+                .chars()
+                .any(|c| disallowed.contains(&format!("-{}", c).as_str()))
+        } else {
+            disallowed.contains(&name)
+        };
+        if hit {
+            return rejected(&format!("{} {}", subcommand, name));
+        }
+    }
+
+    Ok(subcommand.to_string())
+}
+
+/// A flag's name without any `=value`
+fn flag_name(arg: &str) -> &str {
+    arg.split_once('=').map_or(arg, |(name, _)| name)
+}
+
+/// Run a ddev command (e.g. `["xdebug", "on"]`) in each matching project's approot, one
+/// project at a time, streaming per-project output and a success/failure summary at the end
+/// Only project-scoped subcommands are allowed (see `check_bulk_command`)
+/// Can be cancelled between (or during) projects; returns a process ID
+#[tauri::command]
+pub async fn run_across_projects(
+    window: Window,
+    command_args: Vec<String>,
+    filter: Option<ProjectFilter>,
+) -> Result<String, DdevError> {
+    check_bulk_command(&command_args)?;
+
+    let (project_type, status) = filter
+        .map(|f| (f.project_type, f.status))
+        .unwrap_or_default();
+    let projects = list_projects_filtered(status, project_type, None).await?;
+    if projects.is_empty() {
+        return Err(DdevError::CommandFailed(
+            "No projects match the filter".to_string(),
+        ));
+    }
+
+    let process_id = generate_process_id();
+    let command_name = "run-across-projects".to_string();
+    let process_id_clone = process_id.clone();
+    let command_line = command_args.join(" ");
+
+    create_task_entry(&process_id, &command_name, "all");
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: "all".to_string(),
+            status: "started".to_string(),
+            message: Some(format!(
+                "Running ddev {} in {} project(s)",
+                command_line,
+                projects.len()
+            )),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let ddev_cmd = get_ddev_command();
+        let enhanced_path = get_enhanced_path();
+        let mut args = get_ddev_base_args();
+        args.extend(command_args.iter().map(|a| a.as_str()));
+
+        let emit_line = |line: String, stream: &str| {
+            let _ = window.emit("command-output", CommandOutput::new(line, stream));
+        };
+
+        let mut results: Vec<(String, bool)> = Vec::new();
+        for project in &projects {
+            if is_process_cancelled(&process_id_clone) {
+                return;
+            }
+
+            emit_line(
+                format!("=== {}: ddev {} ===", project.name, command_line),
+                "stdout",
+            );
+            match run_streaming_command(
+                &window,
+                &ddev_cmd,
+                &args,
+                &project.approot,
+                &enhanced_path,
+                Some(&process_id_clone),
+                &command_name,
+                &project.name,
+            ) {
+                Ok(success) => results.push((project.name.clone(), success)),
+                // Cancelled - cancel_command already emitted the status
+                Err(_) => return,
+            }
+        }
+
+        emit_line("=== Summary ===".to_string(), "stdout");
+        for (name, success) in &results {
+            let (outcome, stream) = if *success {
+                ("ok", "stdout")
+            } else {
+                ("failed", "stderr")
+            };
+            emit_line(format!("{}: {}", name, outcome), stream);
+        }
+
+        let failed = results.iter().filter(|(_, success)| !success).count();
+        remove_task_entry(&process_id_clone);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project: "all".to_string(),
+                status: if failed == 0 { "finished" } else { "error" }.to_string(),
+                message: Some(format!(
+                    "{} succeeded, {} failed",
+                    results.len() - failed,
+                    failed
+                )),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
+
+/// How long a `describe_project` result can be served from cache when the caller allows it
+const DESCRIBE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Maximum `ddev describe` calls run at once by `prewarm_describe`
+const PREWARM_CONCURRENCY: usize = 3;
+
+// Latest `describe_project` result per project, with when it was fetched
+static DESCRIBE_CACHE: Lazy<Mutex<HashMap<String, (Instant, DdevProjectDetails)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Get a project's cached details if they're younger than `DESCRIBE_CACHE_TTL`
+fn cached_describe(name: &str) -> Option<DdevProjectDetails> {
+    let cache = DESCRIBE_CACHE.lock().unwrap();
+    cache
+        .get(name)
+        .filter(|(fetched_at, _)| fetched_at.elapsed() < DESCRIBE_CACHE_TTL)
+        .map(|(_, details)| details.clone())
+}
+
+/// Drop a project's cached details after an action that changes its status or config
+/// Called both when the action starts and when it ends, so a describe in between can't
+/// leave a mid-transition result in the cache
+fn invalidate_describe_cache(name: &str) {
+    DESCRIBE_CACHE.lock().unwrap().remove(name);
+}
+
 /// Get detailed information about a specific project
+/// With `use_cache`, a recent result (e.g. from `prewarm_describe`) is returned instantly;
+/// otherwise `ddev describe` always runs. Every fresh result refreshes the cache.
+#[tauri::command]
+pub async fn describe_project(
+    name: String,
+    use_cache: Option<bool>,
+) -> Result<DdevProjectDetails, DdevError> {
+    if use_cache.unwrap_or(false) {
+        if let Some(details) = cached_describe(&name) {
+            return Ok(details);
+        }
+    }
+
+    let details = fetch_project_details(&name).await?;
+    DESCRIBE_CACHE
+        .lock()
+        .unwrap()
+        .insert(name, (Instant::now(), details.clone()));
+    Ok(details)
+}
+
+/// Describe several projects in the background (a few at a time) so their detail panels
+/// open instantly; projects with a fresh cache entry are skipped and failures are ignored
 #[tauri::command]
-pub async fn describe_project(name: String) -> Result<DdevProjectDetails, DdevError> {
-    let mut details: DdevProjectDetails = run_ddev_json_command_async(&["describe", &name]).await?;
+pub async fn prewarm_describe(names: Vec<String>) -> Result<(), DdevError> {
+    let permits = Arc::new(tokio::sync::Semaphore::new(PREWARM_CONCURRENCY));
+    let handles: Vec<_> = names
+        .into_iter()
+        .filter(|name| cached_describe(name).is_none())
+        .map(|name| {
+            let permits = permits.clone();
+            tauri::async_runtime::spawn(async move {
+                let _permit = permits.acquire().await;
+                let _ = describe_project(name, Some(true)).await;
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.await;
+    }
+    Ok(())
+}
+
+/// Run `ddev describe` for a project, overlaying runtime state the config doesn't reflect
+async fn fetch_project_details(name: &str) -> Result<DdevProjectDetails, DdevError> {
+    let mut details: DdevProjectDetails = run_ddev_json_command_async(&["describe", name]).await?;
 
     // Override xdebug_enabled with runtime status when project is running,
     // because `ddev describe` reports the config value (xdebug_enabled in .ddev/config.yaml)
@@ -30,11 +581,28 @@ pub async fn describe_project(name: String) -> Result<DdevProjectDetails, DdevEr
         if let Ok(runtime_enabled) = check_xdebug_runtime(&details.approot).await {
             details.xdebug_enabled = runtime_enabled;
         }
+
+        // Likewise `database_version` is the configured value; if it was changed without
+        // recreating the db container, the running server may still be the old version.
+        if let Some(database_type) = details.database_type.clone() {
+            if let Ok(runtime_version) =
+                check_database_version_runtime(&details.approot, &database_type).await
+            {
+                details.database_version_mismatch = details
+                    .database_version
+                    .as_deref()
+                    .is_some_and(|configured| !version_matches(configured, &runtime_version));
+                details.database_version_runtime = Some(runtime_version);
+            }
+        }
     }
 
     Ok(details)
 }
 
+// Serializes xdebug toggles so rapid clicks read the state left by the previous toggle
+static XDEBUG_TOGGLE_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
 /// Check xdebug runtime status by running `ddev xdebug status`
 async fn check_xdebug_runtime(approot: &str) -> Result<bool, DdevError> {
     let ddev_cmd = get_ddev_command();
@@ -59,54 +627,1558 @@ async fn check_xdebug_runtime(approot: &str) -> Result<bool, DdevError> {
     Ok(combined.contains("xdebug enabled"))
 }
 
-/// Start a DDEV project (non-blocking, streams output via events)
-/// Returns a process ID that can be used to cancel the command
-#[tauri::command]
-pub fn start_project(window: Window, name: String) -> Result<String, DdevError> {
-    run_ddev_command_streaming(window, "start", &name, &["start", &name])
+/// Clock skew (seconds) beyond which `check_time_skew` warns
+const TIME_SKEW_WARN_SECS: i64 = 5;
+
+/// Format a Unix timestamp as an ISO 8601 UTC time (e.g. 2024-05-01T12:00:00Z)
+fn format_utc_timestamp(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
 }
 
-/// Stop a DDEV project (non-blocking, streams output via events)
-/// Returns a process ID that can be used to cancel the command
+/// Parse an RFC 3339 timestamp like Docker's "2024-01-15T10:30:45.123456789Z"
+/// (an offset such as "+02:00" instead of "Z" is also accepted)
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let value = value.trim();
+    let (date, time) = value.split_once(['T', 't'])?;
+
+    let mut date_parts = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else {
+        let (clock, offset) = time.split_at(time.rfind(['+', '-'])?);
+        let (hours, minutes) = offset[1..].split_once(':')?;
+        let secs = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        (clock, if offset.starts_with('-') { -secs } else { secs })
+    };
+
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut clock_parts = clock.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (
+        clock_parts.next()??,
+        clock_parts.next()??,
+        clock_parts.next()??,
+    );
+    // Nanoseconds from up to 9 fraction digits
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        let digits: String = fraction.chars().take(9).collect();
+        digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32)
+    };
+
+    // Days since 1970-01-01 from a civil date (inverse of `format_utc_timestamp`)
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    let secs = u64::try_from(secs).ok()?;
+    Some(UNIX_EPOCH + Duration::new(secs, nanos))
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Compare the host clock with a running project's web container clock
+/// Container clocks can drift after the host sleeps (some VM backends), which breaks TLS
+/// and caching in confusing ways; restarting Docker resyncs them
 #[tauri::command]
-pub fn stop_project(window: Window, name: String) -> Result<String, DdevError> {
-    run_ddev_command_streaming(window, "stop", &name, &["stop", &name])
+pub async fn check_time_skew(project: String, approot: String) -> Result<TimeSkew, DdevError> {
+    let mut args = get_ddev_base_args();
+    args.extend_from_slice(&["exec", "date", "+%s"]);
+
+    let before = unix_now();
+    let output = AsyncCommand::new(get_ddev_command())
+        .args(&args)
+        .current_dir(&approot)
+        .env("PATH", get_enhanced_path())
+        .output()
+        .await
+        .map_err(|e| DdevError::IoError(e.to_string()))?;
+    let after = unix_now();
+
+    if !output.status.success() {
+        return Err(DdevError::CommandFailed(format!(
+            "Couldn't read the clock in {}: {}",
+            project,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let container_secs: i64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|e| DdevError::ParseError(format!("Unexpected date output: {}", e)))?;
+    // Compare against the middle of the exec round trip to discount its latency
+    let host_secs = before + (after - before) / 2;
+    let skew_seconds = container_secs - host_secs;
+
+    let warning = (skew_seconds.abs() > TIME_SKEW_WARN_SECS).then(|| {
+        format!(
+            "The {} container clock is {} seconds {} the host. This can break HTTPS and \
+             caching; restart Docker to resync it.",
+            project,
+            skew_seconds.abs(),
+            if skew_seconds > 0 {
+                "ahead of"
+            } else {
+                "behind"
+            }
+        )
+    });
+
+    Ok(TimeSkew {
+        host_time: format_utc_timestamp(host_secs),
+        container_time: format_utc_timestamp(container_secs),
+        skew_seconds,
+        warning,
+    })
 }
 
-/// Restart a DDEV project (non-blocking, streams output via events)
-/// Returns a process ID that can be used to cancel the command
+/// Query the running database server for its version
+async fn check_database_version_runtime(
+    approot: &str,
+    database_type: &str,
+) -> Result<String, DdevError> {
+    let query_args: &[&str] = if database_type == "postgres" {
+        &[
+            "exec",
+            "-s",
+            "db",
+            "psql",
+            "-U",
+            "db",
+            "-tAc",
+            "SHOW server_version",
+            "postgres",
+        ]
+    } else {
+        &["mysql", "-N", "-B", "-e", "SELECT VERSION()"]
+    };
+
+    let mut full_args = get_ddev_base_args();
+    full_args.extend_from_slice(query_args);
+
+    let output = AsyncCommand::new(get_ddev_command())
+        .args(&full_args)
+        .current_dir(approot)
+        .env("PATH", get_enhanced_path())
+        .output()
+        .await
+        .map_err(|e| DdevError::IoError(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(DdevError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .ok_or_else(|| DdevError::ParseError("Database returned no version".to_string()))
+}
+
+/// Check a full server version (e.g. "10.11.6-MariaDB-log", "16.2 (Debian ...)") against
+/// a configured major/minor version such as "10.11" or "16"
+fn version_matches(configured: &str, runtime: &str) -> bool {
+    runtime
+        .strip_prefix(configured)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '-', ' ']))
+}
+
+/// Get the scripts defined in a project's composer.json
+/// Array-valued scripts are joined with " && "; returns empty when there is no composer.json
 #[tauri::command]
-pub fn restart_project(window: Window, name: String) -> Result<String, DdevError> {
-    run_ddev_command_streaming(window, "restart", &name, &["restart", &name])
+pub fn get_composer_scripts(approot: String) -> Result<Vec<ComposerScript>, DdevError> {
+    let path = std::path::Path::new(&approot).join("composer.json");
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => {
+            return Err(DdevError::IoError(format!(
+                "Failed to read composer.json: {}",
+                e
+            )))
+        }
+    };
+
+    let composer: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| DdevError::ParseError(format!("Failed to parse composer.json: {}", e)))?;
+
+    let Some(scripts) = composer.get("scripts").and_then(|s| s.as_object()) else {
+        return Ok(vec![]);
+    };
+
+    Ok(scripts
+        .iter()
+        .filter_map(|(name, value)| {
+            let command = match value {
+                serde_json::Value::String(command) => command.clone(),
+                serde_json::Value::Array(commands) => commands
+                    .iter()
+                    .filter_map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" && "),
+                _ => return None,
+            };
+            Some(ComposerScript {
+                name: name.clone(),
+                command,
+            })
+        })
+        .collect())
 }
 
-/// Power off all DDEV projects (non-blocking, streams output via events)
+/// Run a shell command in the project's web container (`ddev exec`, streaming output)
+/// The command is passed as one argument after `--`, so DDEV hands it to the container's
+/// shell intact (quoting, pipes and `&&` work as typed). Long-running commands (watchers)
+/// keep streaming until cancelled with `cancel_command`.
 /// Returns a process ID that can be used to cancel the command
 #[tauri::command]
-pub fn poweroff(window: Window) -> Result<String, DdevError> {
-    run_ddev_command_streaming(window, "poweroff", "all", &["poweroff"])
+pub fn exec_command(
+    window: Window,
+    project: String,
+    approot: String,
+    command: String,
+) -> Result<String, DdevError> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(DdevError::CommandFailed("No command to run".to_string()));
+    }
+
+    run_ddev_command_streaming_in_dir(window, "exec", &project, &["exec", "--", command], &approot)
 }
 
-/// Delete a DDEV project (removes containers and config, keeps files)
+/// Run a command in the project's web container attached to a terminal (streaming output)
+/// For commands that need a TTY (interactive installers, prompts); answer them with
+/// `send_stdin`. Like `exec_command`, the command goes to the container's shell intact.
 /// Returns a process ID that can be used to cancel the command
 #[tauri::command]
-pub fn delete_project(window: Window, name: String) -> Result<String, DdevError> {
-    run_ddev_command_streaming(
+pub fn exec_interactive(
+    window: Window,
+    project: String,
+    approot: String,
+    command: String,
+) -> Result<String, DdevError> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err(DdevError::CommandFailed("No command to run".to_string()));
+    }
+
+    let mut args = get_ddev_base_args();
+    args.extend_from_slice(&["exec", "--", command]);
+    run_pty_command(
         window,
-        "delete",
-        &name,
-        &["delete", "--omit-snapshot", "--yes", &name],
+        "exec-interactive",
+        &project,
+        &get_ddev_command(),
+        &args,
+        &approot,
+        &get_enhanced_path(),
     )
 }
 
-/// Change a project configuration option and optionally restart
-/// Generic helper for config changes
+/// Get every host port published by a project's services
+/// Flattens `host_ports_mapping` across all services from `ddev describe`
+#[tauri::command]
+pub async fn get_published_ports(name: String) -> Result<Vec<PublishedPort>, DdevError> {
+    let details: DdevProjectDetails = run_ddev_json_command_async(&["describe", &name]).await?;
+
+    let mut ports: Vec<PublishedPort> = details
+        .services
+        .into_iter()
+        .flat_map(|(service, info)| {
+            info.host_ports_mapping
+                .into_iter()
+                .filter(|mapping| !mapping.host_port.is_empty())
+                .map(move |mapping| PublishedPort {
+                    service: service.clone(),
+                    exposed: mapping.exposed_port,
+                    host: mapping.host_port,
+                })
+        })
+        .collect();
+
+    // Services come from a HashMap, so sort for a stable table order
+    ports.sort_by(|a, b| a.service.cmp(&b.service).then(a.exposed.cmp(&b.exposed)));
+
+    Ok(ports)
+}
+
+/// Host ports published by a project's own containers, parsed from `docker ps` port lists
+/// like "0.0.0.0:8025->8025/tcp, :::8025->8025/tcp"
+async fn own_container_ports(name: &str) -> HashSet<u16> {
+    let label = format!("label=com.ddev.site-name={}", name);
+    let Ok(output) =
+        run_docker_command_async(&["ps", "--filter", &label, "--format", "{{.Ports}}"]).await
+    else {
+        return HashSet::new();
+    };
+
+    output
+        .split([',', '\n'])
+        .filter_map(|binding| {
+            let (host, _) = binding.split_once("->")?;
+            host.rsplit(':').next()?.trim().parse().ok()
+        })
+        .collect()
+}
+
+/// Check whether the host ports a project publishes are free, to warn before starting it
+/// Ports come from `host_ports_mapping` in `ddev describe`; each is probed by binding it on
+/// 127.0.0.1. Ports held by the project's own running containers are not conflicts.
+#[tauri::command]
+pub async fn check_port_conflicts(name: String) -> Result<Vec<PortCheck>, DdevError> {
+    let details = describe_project(name.clone(), None).await?;
+    let own_ports = own_container_ports(&name).await;
+
+    let mut checks: Vec<PortCheck> = details
+        .services
+        .into_iter()
+        .flat_map(|(service, info)| {
+            info.host_ports_mapping
+                .into_iter()
+                .filter_map(move |mapping| {
+                    let port = mapping.host_port.trim().parse::<u16>().ok()?;
+                    Some((service.clone(), port))
+                })
+        })
+        .map(|(service, port)| PortCheck {
+            port,
+            in_use: !own_ports.contains(&port)
+                && std::net::TcpListener::bind(("127.0.0.1", port)).is_err(),
+            service,
+        })
+        .collect();
+
+    checks.sort_by(|a, b| a.port.cmp(&b.port).then(a.service.cmp(&b.service)));
+    checks.dedup_by(|a, b| a.port == b.port && a.service == b.service);
+    Ok(checks)
+}
+
+/// Find host ports published by more than one service across all running projects
+/// DDEV's dynamic ports never collide, but `host_*_port` settings pinned to the same port in
+/// several projects do
+#[tauri::command]
+pub async fn detect_port_conflicts_global() -> Result<Vec<PortConflict>, DdevError> {
+    let projects: Vec<DdevProjectBasic> = run_ddev_json_command_async(&["list"]).await?;
+
+    let mut claims: BTreeMap<String, Vec<PortClaim>> = BTreeMap::new();
+    for project in projects.into_iter().filter(|p| p.status == "running") {
+        let Ok(ports) = get_published_ports(project.name.clone()).await else {
+            continue;
+        };
+        for port in ports {
+            claims.entry(port.host).or_default().push(PortClaim {
+                project: project.name.clone(),
+                service: port.service,
+            });
+        }
+    }
+
+    Ok(claims
+        .into_iter()
+        .filter_map(|(port, mut services)| {
+            services.sort();
+            services.dedup();
+            (services.len() > 1).then_some(PortConflict { port, services })
+        })
+        .collect())
+}
+
+/// Get the URL to open for a project, preferring https or http when both exist
+/// Without an explicit preference, https is preferred only once the local CA is known to be
+/// trusted, so "Open" doesn't land on a certificate warning on machines without mkcert.
+/// Falls back to the other scheme when the preferred one has no URL; with the router
+/// disabled, the web container's direct host-port URL is returned instead
+#[tauri::command]
+pub async fn get_primary_url(
+    name: String,
+    prefer_https: Option<bool>,
+) -> Result<String, DdevError> {
+    let details: DdevProjectDetails = run_ddev_json_command_async(&["describe", &name]).await?;
+
+    let (https, http) = if details.router_disabled {
+        let web = details.services.get("web");
+        (
+            web.and_then(|w| w.host_https_url.clone())
+                .unwrap_or_default(),
+            web.and_then(|w| w.host_http_url.clone())
+                .unwrap_or_default(),
+        )
+    } else {
+        (details.httpsurl, details.httpurl)
+    };
+
+    let prefer_https = match prefer_https {
+        Some(prefer) => prefer,
+        None if https.is_empty() || http.is_empty() => true,
+        None => check_ca_installed(&https).await.unwrap_or(false),
+    };
+
+    let (preferred, other) = if prefer_https {
+        (https, http)
+    } else {
+        (http, https)
+    };
+
+    [preferred, other, details.primary_url]
+        .into_iter()
+        .find(|url| !url.is_empty())
+        .ok_or_else(|| DdevError::CommandFailed(format!("No URL available for {}", name)))
+}
+
+/// Copy every URL a project exposes to the clipboard, one per line
+/// Returns the copied text so the UI can show what was copied
+#[tauri::command]
+pub async fn copy_all_urls(app: tauri::AppHandle, name: String) -> Result<String, DdevError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let details: DdevProjectDetails = run_ddev_json_command_async(&["describe", &name]).await?;
+
+    let candidates = std::iter::once(details.primary_url)
+        .chain(details.https_urls)
+        .chain(details.http_urls)
+        .chain([
+            details.mailpit_https_url,
+            details.mailpit_url,
+            details.xhgui_https_url,
+            details.xhgui_url,
+        ]);
+
+    // Deduplicate while keeping the primary URL first
+    let mut urls: Vec<String> = Vec::new();
+    for url in candidates {
+        if !url.is_empty() && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+
+    let text = urls.join("\n");
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| DdevError::IoError(format!("Failed to write to clipboard: {}", e)))?;
+
+    Ok(text)
+}
+
+/// Get the images (and their digests, when pulled) used by a project's web and db containers
+/// DDEV runs containers from locally built `*-built` images, which have no repo digest, so
+/// the digests are those of the base images they're built from
+#[tauri::command]
+pub async fn get_image_info(project: String, approot: String) -> Result<ImageInfo, DdevError> {
+    let details: DdevProjectDetails =
+        run_ddev_json_command_in_dir_async(&["describe", &project], &approot).await?;
+    let web_image = details.webimg.unwrap_or_default();
+    let db_image = details.dbimg.unwrap_or_default();
+
+    Ok(ImageInfo {
+        web_digest: get_image_digest(&web_image).await,
+        web_image,
+        db_digest: get_image_digest(&db_image).await,
+        db_image,
+    })
+}
+
+/// Resolve the repo digest of a local image; None if it isn't pulled or has no digest
+async fn get_image_digest(image: &str) -> Option<String> {
+    if image.is_empty() {
+        return None;
+    }
+    let digest = run_docker_command_async(&[
+        "image",
+        "inspect",
+        "--format",
+        "{{if .RepoDigests}}{{index .RepoDigests 0}}{{end}}",
+        image,
+    ])
+    .await
+    .ok()?;
+
+    let digest = digest.trim();
+    if digest.is_empty() {
+        None
+    } else {
+        Some(digest.to_string())
+    }
+}
+
+/// Check whether a project's web and db images are present locally
+/// A service without an image (e.g. omitted db) counts as present; all false if Docker is down
+#[tauri::command]
+pub async fn images_present(project: String, approot: String) -> Result<ImagesPresent, DdevError> {
+    let not_present = ImagesPresent {
+        web: false,
+        db: false,
+    };
+
+    if run_docker_command_async(&["info", "--format", "{{.ServerVersion}}"])
+        .await
+        .is_err()
+    {
+        return Ok(not_present);
+    }
+
+    let details: DdevProjectDetails =
+        match run_ddev_json_command_in_dir_async(&["describe", &project], &approot).await {
+            Ok(details) => details,
+            Err(_) => return Ok(not_present),
+        };
+
+    Ok(ImagesPresent {
+        web: is_image_present(details.webimg.as_deref()).await,
+        db: is_image_present(details.dbimg.as_deref()).await,
+    })
+}
+
+/// Check if an image exists in the local Docker image store
+async fn is_image_present(image: Option<&str>) -> bool {
+    match image.filter(|i| !i.is_empty()) {
+        Some(image) => {
+            run_docker_command_async(&["image", "inspect", "--format", "{{.Id}}", image])
+                .await
+                .is_ok()
+        }
+        None => true,
+    }
+}
+
+/// Compose file DDEV generates on start, used to pull images when download-images is unavailable
+const FULL_COMPOSE_FILE: &str = ".ddev/.ddev-docker-compose-full.yaml";
+
+/// Command (program and args) that downloads a project's images without starting it, if any
+fn image_pull_command(approot: &str) -> Option<(String, Vec<&'static str>)> {
+    let ddev_cmd = get_ddev_command();
+    let base_args = get_ddev_base_args();
+
+    // `ddev debug --help` lists the subcommands this DDEV version supports
+    let mut help_args = base_args.clone();
+    help_args.extend_from_slice(&["debug", "--help"]);
+    let has_download_images = Command::new(&ddev_cmd)
+        .args(&help_args)
+        .env("PATH", get_enhanced_path())
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains("download-images"))
+        .unwrap_or(false);
+
+    if has_download_images {
+        let mut args = base_args;
+        args.extend_from_slice(&["debug", "download-images"]);
+        Some((ddev_cmd, args))
+    } else if std::path::Path::new(approot)
+        .join(FULL_COMPOSE_FILE)
+        .is_file()
+    {
+        Some((
+            "docker".to_string(),
+            vec!["compose", "-f", FULL_COMPOSE_FILE, "pull"],
+        ))
+    } else {
+        None
+    }
+}
+
+/// Pre-pull a project's images (streaming output) so a subsequent start isn't slowed by downloads
+/// Uses `ddev debug download-images` when this DDEV has it, otherwise `docker compose pull`
+/// on the compose file from the last start
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn pull_images(window: Window, project: String, approot: String) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let command_name = "pull-images".to_string();
+    let process_id_clone = process_id.clone();
+
+    create_task_entry(&process_id, &command_name, &project);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project.clone(),
+            status: "started".to_string(),
+            message: Some("Pulling images".to_string()),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let enhanced_path = get_enhanced_path();
+        let Some((cmd, args)) = image_pull_command(&approot) else {
+            remove_task_entry(&process_id_clone);
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name,
+                    project,
+                    status: "error".to_string(),
+                    message: Some(
+                        "This DDEV version can't download images ahead of the first start"
+                            .to_string(),
+                    ),
+                    process_id: None,
+                },
+            );
+            return;
+        };
+
+        let _ = window.emit(
+            "command-output",
+            CommandOutput::new(format!("Running: {} {}", cmd, args.join(" ")), "stdout"),
+        );
+
+        let (status, message) = match run_streaming_command(
+            &window,
+            &cmd,
+            &args,
+            &approot,
+            &enhanced_path,
+            Some(&process_id_clone),
+            &command_name,
+            &project,
+        ) {
+            Ok(true) => ("finished", "Images downloaded"),
+            Ok(false) => ("error", "Failed to pull images"),
+            // Cancelled - cancel_command already emitted the status
+            Err(_) => return,
+        };
+
+        remove_task_entry(&process_id_clone);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project,
+                status: status.to_string(),
+                message: Some(message.to_string()),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
+
+/// Start a DDEV project (non-blocking, streams output via events)
+/// Successful start durations are recorded for `get_start_times`
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn start_project(window: Window, name: String) -> Result<String, DdevError> {
+    let project = name.clone();
+    invalidate_describe_cache(&name);
+    run_ddev_command_streaming_with_callback(
+        window,
+        "start",
+        &name,
+        &["start", &name],
+        true,
+        move |outcome| {
+            invalidate_describe_cache(&project);
+            if outcome.success {
+                let _ = record_start_time(&project, outcome.elapsed.as_millis() as u64);
+            }
+            let _ = record_start_result(
+                &project,
+                StartResult {
+                    status: if outcome.success {
+                        "succeeded"
+                    } else {
+                        "failed"
+                    }
+                    .to_string(),
+                    error_summary: outcome.error_summary(),
+                },
+            );
+        },
+    )
+}
+
+/// Get the outcome of a project's most recent start from the app, with the error lines of
+/// a failed start (status "unknown" if it hasn't been started from the app yet)
+#[tauri::command]
+pub fn get_last_start_result(name: String) -> StartResult {
+    load_settings()
+        .last_start_results
+        .remove(&name)
+        .unwrap_or_else(|| StartResult {
+            status: "unknown".to_string(),
+            error_summary: None,
+        })
+}
+
+/// How long `first_start` waits for the site to answer after `ddev start`
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Poll a started project's HTTP URL until the web server answers (any non-5xx response)
+/// Gives up after `READY_TIMEOUT` or when the task is cancelled
+async fn wait_until_ready(name: &str, process_id: &str) -> bool {
+    let Ok(details) = run_ddev_json_command_async::<DdevProjectDetails>(&["describe", name]).await
+    else {
+        return false;
+    };
+    let Ok(client) = http_client() else {
+        return false;
+    };
+
+    let deadline = Instant::now() + READY_TIMEOUT;
+    while Instant::now() < deadline && !is_process_cancelled(process_id) {
+        let ready = client
+            .get(&details.httpurl)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .is_ok_and(|response| !response.status().is_server_error());
+        if ready {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    false
+}
+
+/// Start a project the slow first time, as one cancellable task with `task-phase` events:
+/// check images → pull them if missing (streaming) → `ddev start` → wait for the site to answer
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn first_start(window: Window, name: String, approot: String) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let command_name = "first-start".to_string();
+    let process_id_clone = process_id.clone();
+
+    create_task_entry(&process_id, &command_name, &name);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: name.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Starting {} for the first time", name)),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let enhanced_path = get_enhanced_path();
+        let emit_phase = |phase: &str, message: String| {
+            let _ = window.emit(
+                "task-phase",
+                TaskPhase {
+                    process_id: process_id_clone.clone(),
+                    command: command_name.clone(),
+                    project: name.clone(),
+                    phase: phase.to_string(),
+                    message,
+                },
+            );
+        };
+        let finish = |status: &str, message: String| {
+            remove_task_entry(&process_id_clone);
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name.clone(),
+                    project: name.clone(),
+                    status: status.to_string(),
+                    message: Some(message),
+                    process_id: None,
+                },
+            );
+        };
+
+        // Phase 1: check which images are already downloaded
+        emit_phase("images", "Checking for project images".to_string());
+        let images = tauri::async_runtime::block_on(images_present(name.clone(), approot.clone()));
+        if is_process_cancelled(&process_id_clone) {
+            return;
+        }
+
+        // Phase 2: pull missing images with visible progress; otherwise `ddev start` pulls them
+        let missing = images
+            .map(|images| !images.web || !images.db)
+            .unwrap_or(true);
+        match image_pull_command(&approot).filter(|_| missing) {
+            Some((cmd, args)) => {
+                emit_phase("pull", "Downloading images".to_string());
+                match run_streaming_command(
+                    &window,
+                    &cmd,
+                    &args,
+                    &approot,
+                    &enhanced_path,
+                    Some(&process_id_clone),
+                    &command_name,
+                    &name,
+                ) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        finish("error", "Failed to pull images".to_string());
+                        return;
+                    }
+                    // Cancelled - cancel_command already emitted the status
+                    Err(_) => return,
+                }
+            }
+            None if missing => {
+                emit_phase("pull", "Images will be downloaded during start".to_string())
+            }
+            None => emit_phase("pull", "Images already present".to_string()),
+        }
+
+        // Phase 3: ddev start
+        emit_phase("start", format!("Starting {}", name));
+        let mut start_args = get_ddev_base_args();
+        start_args.extend_from_slice(&["start", &name]);
+        invalidate_describe_cache(&name);
+        let started = run_streaming_command_with_outcome(
+            &window,
+            &get_ddev_command(),
+            &start_args,
+            &approot,
+            &enhanced_path,
+            Some(&process_id_clone),
+            &command_name,
+            &name,
+        );
+        invalidate_describe_cache(&name);
+        match started {
+            Ok(outcome) => {
+                let _ = record_start_result(
+                    &name,
+                    StartResult {
+                        status: if outcome.success {
+                            "succeeded"
+                        } else {
+                            "failed"
+                        }
+                        .to_string(),
+                        error_summary: outcome.error_summary(),
+                    },
+                );
+                if !outcome.success {
+                    finish("error", format!("Failed to start {}", name));
+                    return;
+                }
+                let _ = record_start_time(&name, outcome.elapsed.as_millis() as u64);
+            }
+            // Cancelled - cancel_command already emitted the status
+            Err(_) => return,
+        }
+
+        // Phase 4: wait until the site actually answers
+        emit_phase("ready", "Waiting for the site to respond".to_string());
+        let ready = tauri::async_runtime::block_on(wait_until_ready(&name, &process_id_clone));
+        if is_process_cancelled(&process_id_clone) {
+            return;
+        }
+
+        if ready {
+            finish("finished", format!("{} is up and running", name));
+        } else {
+            finish(
+                "finished",
+                format!(
+                    "{} started, but the site didn't respond within {} seconds",
+                    name,
+                    READY_TIMEOUT.as_secs()
+                ),
+            );
+        }
+    });
+
+    Ok(process_id)
+}
+
+/// Find registered projects whose project directory no longer exists
+/// Only reports directories that are definitely gone; paths that can't be checked
+/// (e.g. permission errors or unmounted drives reporting an error) are left alone
+#[tauri::command]
+pub async fn get_orphaned_projects() -> Result<Vec<String>, DdevError> {
+    let projects: Vec<DdevProjectBasic> = run_ddev_json_command_async(&["list"]).await?;
+
+    Ok(projects
+        .into_iter()
+        .filter(|project| {
+            !project.approot.is_empty()
+                && matches!(
+                    std::path::Path::new(&project.approot).try_exists(),
+                    Ok(false)
+                )
+        })
+        .map(|project| project.name)
+        .collect())
+}
+
+/// Remove the registration of a project whose directory no longer exists
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn cleanup_orphaned_project(window: Window, name: String) -> Result<String, DdevError> {
+    let project = name.clone();
+    run_ddev_command_streaming_with_callback(
+        window,
+        "cleanup-orphaned",
+        &name,
+        &["delete", "--omit-snapshot", "--yes", &name],
+        false,
+        move |outcome| {
+            if outcome.success {
+                let _ = remove_project_note(&project);
+            }
+        },
+    )
+}
+
+/// Get the recent start durations for a project in milliseconds, oldest first
+#[tauri::command]
+pub fn get_start_times(name: String) -> Result<Vec<u64>, DdevError> {
+    Ok(load_settings()
+        .start_times
+        .get(&name)
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Save a free-form note for a project; an empty (or all-whitespace) note removes it
+/// Notes may span several lines and are kept as written
+#[tauri::command]
+pub fn set_project_note(name: String, note: String) -> Result<(), DdevError> {
+    update_settings(|settings| {
+        if note.trim().is_empty() {
+            settings.project_notes.remove(&name);
+        } else {
+            settings.project_notes.insert(name, note);
+        }
+    })
+}
+
+/// Get a project's note, if it has one
+#[tauri::command]
+pub fn get_project_note(name: String) -> Option<String> {
+    load_settings().project_notes.remove(&name)
+}
+
+/// How often `watch_project_boot` polls `ddev describe`
+const BOOT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Give up watching a boot after this long, even if the start is still running
+const BOOT_WATCH_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Commands whose progress `watch_project_boot` follows
+const BOOT_COMMANDS: &[&str] = &["start", "restart"];
+
+/// Follow a project's services while it starts, emitting a `service-status` event whenever
+/// one changes state. Stops once every service is healthy or the start/restart task ends.
+#[tauri::command]
+pub async fn watch_project_boot(
+    window: Window,
+    name: String,
+    _approot: String,
+) -> Result<(), DdevError> {
+    let started = Instant::now();
+    let mut last_status: HashMap<String, String> = HashMap::new();
+
+    while started.elapsed() < BOOT_WATCH_TIMEOUT {
+        tokio::time::sleep(BOOT_POLL_INTERVAL).await;
+
+        // The start task registers asynchronously, so allow it a moment to appear
+        let boot_running = is_command_running(&name, BOOT_COMMANDS);
+        if !boot_running && started.elapsed() > Duration::from_secs(3) {
+            break;
+        }
+
+        let details: DdevProjectDetails =
+            match run_ddev_json_command_async(&["describe", &name]).await {
+                Ok(details) => details,
+                // Containers may not exist yet early in the start
+                Err(_) => continue,
+            };
+
+        for (service, info) in &details.services {
+            if last_status.get(service) == Some(&info.status) {
+                continue;
+            }
+            last_status.insert(service.clone(), info.status.clone());
+            let _ = window.emit(
+                "service-status",
+                ServiceStatus {
+                    project: name.clone(),
+                    service: service.clone(),
+                    status: info.status.clone(),
+                    healthy: is_service_healthy(&info.status),
+                },
+            );
+        }
+
+        if !details.services.is_empty()
+            && details
+                .services
+                .values()
+                .all(|info| is_service_healthy(&info.status))
+        {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a service status from `ddev describe` means it is up
+fn is_service_healthy(status: &str) -> bool {
+    matches!(status, "running" | "healthy")
+}
+
+/// Stop a DDEV project (non-blocking, streams output via events)
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn stop_project(window: Window, name: String) -> Result<String, DdevError> {
+    let project = name.clone();
+    invalidate_describe_cache(&name);
+    run_ddev_command_streaming_with_callback(
+        window,
+        "stop",
+        &name,
+        &["stop", &name],
+        false,
+        move |_| invalidate_describe_cache(&project),
+    )
+}
+
+/// Restart a DDEV project (non-blocking, streams output via events)
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn restart_project(window: Window, name: String) -> Result<String, DdevError> {
+    let project = name.clone();
+    invalidate_describe_cache(&name);
+    run_ddev_command_streaming_with_callback(
+        window,
+        "restart",
+        &name,
+        &["restart", &name],
+        true,
+        move |_| invalidate_describe_cache(&project),
+    )
+}
+
+/// `.ddev/` entries whose changes never require a restart (data, generated or runtime files)
+const RESTART_IGNORED: &[&str] = &["db_snapshots", "import-db", "mutagen", "traefik", "xhprof"];
+
+/// Marker DDEV puts in files it (re)writes itself on start
+const DDEV_GENERATED_MARKER: &str = "#ddev-generated";
+
+/// Newest modification time of the user-editable config files under `.ddev/`
+fn newest_config_mtime(dir: &std::path::Path, top_level: bool) -> Option<SystemTime> {
+    let mut newest: Option<SystemTime> = None;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        // Top-level dot entries are generated (.ddev-docker-compose-*.yaml, .webimageBuild, ...)
+        if (top_level && file_name.starts_with('.'))
+            || RESTART_IGNORED.contains(&file_name.as_str())
+        {
+            continue;
+        }
+
+        let path = entry.path();
+        let modified = if path.is_dir() {
+            newest_config_mtime(&path, false)
+        } else {
+            let generated = std::fs::read_to_string(&path)
+                .map(|content| content.contains(DDEV_GENERATED_MARKER))
+                .unwrap_or(false);
+            if generated {
+                None
+            } else {
+                entry.metadata().and_then(|m| m.modified()).ok()
+            }
+        };
+        newest = newest.max(modified);
+    }
+    newest
+}
+
+/// Check whether a project's config changed since its web container last started
+/// Compares the newest user-edited file under `.ddev/` with the container's start time;
+/// a stopped or missing container always needs a (re)start
+#[tauri::command]
+pub async fn project_needs_restart(name: String, approot: String) -> Result<bool, DdevError> {
+    let container = format!("ddev-{}-web", name);
+    let state = match run_docker_command_async(&[
+        "inspect",
+        "--format",
+        "{{.State.Running}} {{.State.StartedAt}}",
+        &container,
+    ])
+    .await
+    {
+        Ok(state) => state,
+        // Missing container (never started, or removed by `ddev stop`)
+        Err(DdevError::CommandFailed(message)) if message.to_lowercase().contains("no such") => {
+            return Ok(true)
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut parts = state.split_whitespace();
+    if parts.next() != Some("true") {
+        return Ok(true);
+    }
+    let started_at = parts.next().and_then(parse_rfc3339).ok_or_else(|| {
+        DdevError::ParseError(format!(
+            "Unexpected container state from docker: {}",
+            state.trim()
+        ))
+    })?;
+
+    let ddev_dir = std::path::Path::new(&approot).join(".ddev");
+    Ok(newest_config_mtime(&ddev_dir, true).is_some_and(|modified| modified > started_at))
+}
+
+/// Restart a project only if its config changed since it started (see `project_needs_restart`)
+/// Returns the restart's process ID, or None after emitting a "finished" status straight away
+/// when no restart is needed
+#[tauri::command]
+pub async fn smart_restart(
+    window: Window,
+    name: String,
+    approot: String,
+) -> Result<Option<String>, DdevError> {
+    if project_needs_restart(name.clone(), approot).await? {
+        return restart_project(window, name).map(Some);
+    }
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: "restart".to_string(),
+            project: name,
+            status: "finished".to_string(),
+            message: Some("No restart needed: config unchanged since last start".to_string()),
+            process_id: None,
+        },
+    );
+    Ok(None)
+}
+
+/// PHP ini override file managed by `set_php_ini_override`
+const PHP_INI_OVERRIDE: &str = ".ddev/php/ddev-php.ini";
+
+/// Write PHP ini directives (e.g. `memory_limit=512M`) to `.ddev/php/ddev-php.ini`
+/// Directives already in the file are updated in place and new ones appended; other lines are
+/// kept. With `restart`, the project is restarted so PHP picks them up and the process ID of the
+/// restart is returned.
+#[tauri::command]
+pub fn set_php_ini_override(
+    window: Window,
+    approot: String,
+    settings: HashMap<String, String>,
+    restart: bool,
+) -> Result<Option<String>, DdevError> {
+    if settings.is_empty() {
+        return Err(DdevError::CommandFailed(
+            "No PHP settings given".to_string(),
+        ));
+    }
+    for (key, value) in &settings {
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        if !valid_key {
+            return Err(DdevError::CommandFailed(format!(
+                "Invalid PHP ini directive: {}",
+                key
+            )));
+        }
+        if value.contains(['\n', '\r']) {
+            return Err(DdevError::CommandFailed(format!(
+                "Invalid value for {}: must be a single line",
+                key
+            )));
+        }
+    }
+
+    let path = std::path::Path::new(&approot).join(PHP_INI_OVERRIDE);
+    let existing = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| "; Managed by DDEV Manager\n[PHP]\n".to_string());
+
+    // Sorted so appended directives are written in a stable order
+    let mut pending: Vec<(&String, &String)> = settings.iter().collect();
+    pending.sort();
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in existing.lines() {
+        let directive = line
+            .split_once('=')
+            .map(|(key, _)| key.trim())
+            .filter(|key| !key.starts_with(';'));
+        match directive.and_then(|key| pending.iter().position(|(k, _)| k.as_str() == key)) {
+            Some(index) => {
+                let (key, value) = pending.remove(index);
+                lines.push(format!("{} = {}", key, value));
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    lines.extend(
+        pending
+            .into_iter()
+            .map(|(key, value)| format!("{} = {}", key, value)),
+    );
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| DdevError::IoError(format!("Failed to create .ddev/php: {}", e)))?;
+    }
+    write_atomic(&path, lines.join("\n") + "\n")
+        .map_err(|e| DdevError::IoError(format!("Failed to write {}: {}", PHP_INI_OVERRIDE, e)))?;
+
+    if !restart {
+        return Ok(None);
+    }
+
+    let project_name = project_name_from_config(&approot);
+    run_ddev_command_streaming_in_dir(window, "restart", &project_name, &["restart"], &approot)
+        .map(Some)
+}
+
+/// Read a project's name from `.ddev/config.yaml`, falling back to the folder name as DDEV does
+pub(crate) fn project_name_from_config(approot: &str) -> String {
+    let root = std::path::Path::new(approot);
+    std::fs::read_to_string(root.join(".ddev").join("config.yaml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|config| config.get("name")?.as_str().map(|n| n.to_string()))
+        .unwrap_or_else(|| {
+            root.file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+}
+
+/// Regenerate the TLS certificates for a project (non-blocking, streams output via events)
+/// Removes the project's generated certificate, both from `.ddev/traefik/certs` (which DDEV
+/// copies to the router on start) and the router's own copy in `~/.ddev/traefik/certs`,
+/// then restarts it so DDEV mints a fresh one covering the current hostnames.
+/// `name` must be a registered project whose approot is `approot`.
+/// Note: browsers may still cache the old certificate until their cert cache is cleared.
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn regenerate_certs(
+    window: Window,
+    name: String,
+    approot: String,
+) -> Result<String, DdevError> {
+    let registered = get_registered_project_paths()?;
+    let Some(registered_root) = registered
+        .get(&name)
+        .filter(|root| std::path::Path::new(root) == std::path::Path::new(&approot))
+    else {
+        return Err(DdevError::CommandFailed(format!(
+            "{} is not a registered project at {}",
+            name, approot
+        )));
+    };
+
+    let mut certs_dirs = vec![std::path::Path::new(registered_root)
+        .join(".ddev")
+        .join("traefik")
+        .join("certs")];
+    if let Some(home) = dirs::home_dir() {
+        certs_dirs.push(home.join(".ddev").join("traefik").join("certs"));
+    }
+    for certs_dir in certs_dirs {
+        for ext in ["crt", "key"] {
+            let cert_path = certs_dir.join(format!("{}.{}", name, ext));
+            if cert_path.exists() {
+                std::fs::remove_file(&cert_path).map_err(|e| {
+                    DdevError::IoError(format!("Failed to remove stale certificate: {}", e))
+                })?;
+            }
+        }
+    }
+
+    run_ddev_command_streaming_in_dir(
+        window,
+        "regenerate-certs",
+        &name,
+        &["restart"],
+        registered_root,
+    )
+}
+
+/// Power off all DDEV projects (non-blocking, streams output via events)
+/// Once it exits, `docker ps` verifies nothing is left and a `poweroff-complete` event reports
+/// any stuck DDEV containers so they can be force-removed
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn poweroff(window: Window) -> Result<String, DdevError> {
+    let event_window = window.clone();
+    run_ddev_command_streaming_with_callback(
+        window,
+        "poweroff",
+        "all",
+        &["poweroff"],
+        false,
+        move |_| {
+            DESCRIBE_CACHE.lock().unwrap().clear();
+            let containers = list_ddev_containers();
+            if !containers.is_empty() {
+                let _ = event_window.emit(
+                    "command-output",
+                    CommandOutput::new(
+                        format!(
+                            "Warning: {} DDEV container(s) still present: {}",
+                            containers.len(),
+                            containers.join(", ")
+                        ),
+                        "stderr",
+                    ),
+                );
+            }
+            let _ = event_window.emit(
+                "poweroff-complete",
+                PoweroffComplete {
+                    residual_count: containers.len(),
+                    containers,
+                },
+            );
+        },
+    )
+}
+
+/// Names of all DDEV-managed containers (running or not); empty if Docker can't be queried
+fn list_ddev_containers() -> Vec<String> {
+    Command::new("docker")
+        .args([
+            "ps",
+            "--all",
+            "--filter",
+            "label=com.ddev.platform=ddev",
+            "--format",
+            "{{.Names}}",
+        ])
+        .env("PATH", get_enhanced_path())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Volume name suffixes DDEV uses for per-project volumes that may predate the site-name label
+const PROJECT_VOLUME_SUFFIXES: &[&str] = &["-mariadb", "-mysql", "-postgres", "_project_mutagen"];
+
+fn docker_output_lines(output: String) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Preview what deleting a project would remove: its containers and volumes (found by the
+/// `com.ddev.site-name` label plus DDEV's volume naming), and whether snapshots exist
+#[tauri::command]
+pub async fn preview_delete(name: String, approot: String) -> Result<DeletePreview, DdevError> {
+    let label = format!("label=com.ddev.site-name={}", name);
+
+    let containers =
+        run_docker_command_async(&["ps", "--all", "--filter", &label, "--format", "{{.Names}}"])
+            .await
+            .map(docker_output_lines)?;
+
+    let mut volumes =
+        run_docker_command_async(&["volume", "ls", "--filter", &label, "--format", "{{.Name}}"])
+            .await
+            .map(docker_output_lines)?;
+    let named = run_docker_command_async(&["volume", "ls", "--format", "{{.Name}}"])
+        .await
+        .map(docker_output_lines)?;
+    volumes.extend(named.into_iter().filter(|volume| {
+        PROJECT_VOLUME_SUFFIXES
+            .iter()
+            .any(|suffix| volume.strip_suffix(suffix) == Some(name.as_str()))
+    }));
+    volumes.sort();
+    volumes.dedup();
+
+    let snapshots_dir = std::path::Path::new(&approot)
+        .join(".ddev")
+        .join("db_snapshots");
+    let has_snapshots = std::fs::read_dir(&snapshots_dir)
+        .map(|mut entries| entries.any(|entry| entry.is_ok()))
+        .unwrap_or(false);
+
+    Ok(DeletePreview {
+        containers,
+        volumes,
+        keeps_files: true,
+        has_snapshots,
+    })
+}
+
+/// Delete a DDEV project (removes containers and config, keeps files)
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn delete_project(window: Window, name: String) -> Result<String, DdevError> {
+    let project = name.clone();
+    run_ddev_command_streaming_with_callback(
+        window,
+        "delete",
+        &name,
+        &["delete", "--omit-snapshot", "--yes", &name],
+        false,
+        move |outcome| {
+            if outcome.success {
+                let _ = remove_project_note(&project);
+            }
+        },
+    )
+}
+
+/// Number of automatic config.yaml backups kept per project (oldest are pruned)
+const MAX_CONFIG_BACKUPS: usize = 20;
+
+fn config_backups_dir(approot: &str) -> std::path::PathBuf {
+    std::path::Path::new(approot).join(".ddev").join(".backups")
+}
+
+/// Parse the timestamp out of a `config.<millis>.yaml` backup file name
+fn config_backup_timestamp(file_name: &str) -> Option<u64> {
+    file_name
+        .strip_prefix("config.")?
+        .strip_suffix(".yaml")?
+        .parse()
+        .ok()
+}
+
+/// Copy `.ddev/config.yaml` to `.ddev/.backups/config.<millis>.yaml` and prune old backups
+/// Returns the backup file name, or None when the project has no config.yaml yet
+fn backup_project_config(approot: &str) -> Result<Option<String>, DdevError> {
+    let config_path = std::path::Path::new(approot)
+        .join(".ddev")
+        .join("config.yaml");
+    let content = match std::fs::read(&config_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(DdevError::IoError(e.to_string())),
+    };
+
+    let backups_dir = config_backups_dir(approot);
+    std::fs::create_dir_all(&backups_dir).map_err(|e| DdevError::IoError(e.to_string()))?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let backup_name = format!("config.{}.yaml", millis);
+    write_atomic(&backups_dir.join(&backup_name), content)
+        .map_err(|e| DdevError::IoError(e.to_string()))?;
+
+    for stale in list_config_backups(approot.to_string())?
+        .iter()
+        .skip(MAX_CONFIG_BACKUPS)
+    {
+        let _ = std::fs::remove_file(backups_dir.join(&stale.name));
+    }
+
+    Ok(Some(backup_name))
+}
+
+/// List a project's automatic config.yaml backups, newest first
+#[tauri::command]
+pub fn list_config_backups(approot: String) -> Result<Vec<ConfigBackup>, DdevError> {
+    let entries = match std::fs::read_dir(config_backups_dir(&approot)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(DdevError::IoError(e.to_string())),
+    };
+
+    let mut backups: Vec<ConfigBackup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            config_backup_timestamp(&name).map(|created_at| ConfigBackup { name, created_at })
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+    Ok(backups)
+}
+
+/// Restore `.ddev/config.yaml` from a backup listed by `list_config_backups`
+/// The current config is backed up first, so a restore can itself be undone.
+/// The project must be restarted for the restored config to take effect.
+#[tauri::command]
+pub fn restore_config_backup(approot: String, backup: String) -> Result<(), DdevError> {
+    if config_backup_timestamp(&backup).is_none() {
+        return Err(DdevError::CommandFailed(format!(
+            "'{}' is not a config backup",
+            backup
+        )));
+    }
+
+    let content = std::fs::read(config_backups_dir(&approot).join(&backup))
+        .map_err(|e| DdevError::IoError(format!("Failed to read backup '{}': {}", backup, e)))?;
+
+    backup_project_config(&approot)?;
+    let config_path = std::path::Path::new(&approot)
+        .join(".ddev")
+        .join("config.yaml");
+    write_atomic(&config_path, content).map_err(|e| DdevError::IoError(e.to_string()))?;
+    invalidate_describe_cache(&project_name_from_config(&approot));
+    Ok(())
+}
+
+/// Change one or more project configuration options and optionally restart
+/// Generic helper for config changes; each (flag, value) pair becomes `--{flag}={value}`
 fn change_project_config(
     window: Window,
     name: String,
     approot: String,
-    config_flag: String,
-    config_value: String,
+    config_changes: Vec<(String, String)>,
     command_name: &str,
     success_message: String,
     restart: bool,
@@ -117,6 +2189,19 @@ fn change_project_config(
     let ddev_cmd = get_ddev_command();
     let enhanced_path = get_enhanced_path();
     let process_id_clone = process_id.clone();
+    let changed_flags = config_changes
+        .iter()
+        .map(|(flag, _)| flag.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let change_summary = config_changes
+        .iter()
+        .map(|(flag, value)| format!("{} to {}", flag, value))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // Keep a copy of config.yaml so the change can be rolled back
+    let backup_name = backup_project_config(&approot)?;
 
     // Create an entry in the registry for this multi-step task
     create_task_entry(&process_id, &command_name, &project_name);
@@ -128,7 +2213,7 @@ fn change_project_config(
             command: command_name.clone(),
             project: project_name.clone(),
             status: "started".to_string(),
-            message: Some(format!("Changing {} to {}", config_flag, config_value)),
+            message: Some(format!("Changing {}", change_summary)),
             process_id: Some(process_id.clone()),
         },
     );
@@ -137,19 +2222,34 @@ fn change_project_config(
     thread::spawn(move || {
         let check_cancelled = || -> bool { is_process_cancelled(&process_id_clone) };
 
-        // Step 1: Run ddev config --{flag}={value}
-        let config_arg = format!("--{}={}", config_flag, config_value);
-        let config_args = vec!["config", &config_arg];
+        if let Some(backup_name) = &backup_name {
+            let _ = window.emit(
+                "command-output",
+                CommandOutput::new(
+                    format!("Backed up config.yaml to .ddev/.backups/{}", backup_name),
+                    "stdout",
+                ),
+            );
+        }
+
+        // Step 1: Run ddev config --{flag}={value} ...
+        let flag_args: Vec<String> = config_changes
+            .iter()
+            .map(|(flag, value)| format!("--{}={}", flag, value))
+            .collect();
+        let mut config_args = vec!["config"];
+        config_args.extend(flag_args.iter().map(|s| s.as_str()));
 
         let _ = window.emit(
             "command-output",
-            CommandOutput {
-                line: format!("Running: ddev config {}", config_arg),
-                stream: "stdout".to_string(),
-            },
+            CommandOutput::new(
+                format!("Running: ddev config {}", flag_args.join(" ")),
+                "stdout",
+            ),
         );
 
-        match run_streaming_command(
+        invalidate_describe_cache(&project_name);
+        let configured = run_streaming_command(
             &window,
             &ddev_cmd,
             &config_args,
@@ -158,7 +2258,9 @@ fn change_project_config(
             Some(&process_id_clone),
             &command_name,
             &project_name,
-        ) {
+        );
+        invalidate_describe_cache(&project_name);
+        match configured {
             Ok(true) => {
                 // Config succeeded
                 if restart {
@@ -169,14 +2271,11 @@ fn change_project_config(
 
                     let _ = window.emit(
                         "command-output",
-                        CommandOutput {
-                            line: "Restarting project...".to_string(),
-                            stream: "stdout".to_string(),
-                        },
+                        CommandOutput::new("Restarting project...".to_string(), "stdout"),
                     );
 
                     // Step 2: Run ddev restart
-                    match run_streaming_command(
+                    let restarted = run_streaming_command(
                         &window,
                         &ddev_cmd,
                         &["restart"],
@@ -185,7 +2284,9 @@ fn change_project_config(
                         Some(&process_id_clone),
                         &command_name,
                         &project_name,
-                    ) {
+                    );
+                    invalidate_describe_cache(&project_name);
+                    match restarted {
                         Ok(true) => {
                             // Clean up registry entry
                             remove_task_entry(&process_id_clone);
@@ -242,7 +2343,7 @@ fn change_project_config(
                         command: command_name,
                         project: project_name,
                         status: "error".to_string(),
-                        message: Some(format!("Failed to change {}", config_flag)),
+                        message: Some(format!("Failed to change {}", changed_flags)),
                         process_id: None,
                     },
                 );
@@ -256,19 +2357,184 @@ fn change_project_config(
     Ok(process_id)
 }
 
+/// Re-run DDEV's auto-detection with `ddev config --update` (streaming output)
+/// Waits for completion, reports any detected settings that changed and returns the updated
+/// project details
+#[tauri::command]
+pub async fn reconfigure_project(
+    window: Window,
+    name: String,
+    approot: String,
+) -> Result<DdevProjectDetails, DdevError> {
+    let command_name = "reconfigure".to_string();
+    let process_id = generate_process_id();
+    let before: Option<DdevProjectDetails> =
+        run_ddev_json_command_async(&["describe", &name]).await.ok();
+
+    create_task_entry(&process_id, &command_name, &name);
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: name.clone(),
+            status: "started".to_string(),
+            message: Some("Running: ddev config --update".to_string()),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    let result = {
+        let window = window.clone();
+        let process_id = process_id.clone();
+        let command_name = command_name.clone();
+        let name = name.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            run_streaming_command(
+                &window,
+                &get_ddev_command(),
+                &["config", "--update"],
+                &approot,
+                &get_enhanced_path(),
+                Some(&process_id),
+                &command_name,
+                &name,
+            )
+        })
+        .await
+        .map_err(|e| DdevError::IoError(e.to_string()))?
+    };
+
+    let emit_error = |message: String| {
+        remove_task_entry(&process_id);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name.clone(),
+                project: name.clone(),
+                status: "error".to_string(),
+                message: Some(message.clone()),
+                process_id: None,
+            },
+        );
+        DdevError::CommandFailed(message)
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => return Err(emit_error("Failed to update project config".to_string())),
+        // Cancelled - cancel_command already emitted the status
+        Err(_) => {
+            return Err(DdevError::CommandFailed(
+                "Reconfigure cancelled".to_string(),
+            ))
+        }
+    }
+
+    let after: DdevProjectDetails = match run_ddev_json_command_async(&["describe", &name]).await {
+        Ok(details) => details,
+        Err(e) => return Err(emit_error(format!("Failed to describe project: {}", e))),
+    };
+
+    let changes = before
+        .map(|before| detected_config_changes(&before, &after))
+        .unwrap_or_default();
+    for change in &changes {
+        let _ = window.emit(
+            "command-output",
+            CommandOutput::new(format!("Changed {}", change), "stdout"),
+        );
+    }
+
+    remove_task_entry(&process_id);
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name,
+            project: name,
+            status: "finished".to_string(),
+            message: Some(if changes.is_empty() {
+                "Project reconfigured, no detected settings changed".to_string()
+            } else {
+                format!("Project reconfigured, {} setting(s) changed", changes.len())
+            }),
+            process_id: None,
+        },
+    );
+
+    Ok(after)
+}
+
+/// Describe the auto-detectable settings that differ between two `ddev describe` results
+fn detected_config_changes(before: &DdevProjectDetails, after: &DdevProjectDetails) -> Vec<String> {
+    let fields: [(&str, Option<&str>, Option<&str>); 7] = [
+        (
+            "type",
+            Some(before.project_type.as_str()),
+            Some(after.project_type.as_str()),
+        ),
+        (
+            "docroot",
+            Some(before.docroot.as_str()),
+            Some(after.docroot.as_str()),
+        ),
+        (
+            "php_version",
+            before.php_version.as_deref(),
+            after.php_version.as_deref(),
+        ),
+        (
+            "webserver_type",
+            before.webserver_type.as_deref(),
+            after.webserver_type.as_deref(),
+        ),
+        (
+            "database_type",
+            before.database_type.as_deref(),
+            after.database_type.as_deref(),
+        ),
+        (
+            "database_version",
+            before.database_version.as_deref(),
+            after.database_version.as_deref(),
+        ),
+        (
+            "nodejs_version",
+            Some(before.nodejs_version.as_str()),
+            Some(after.nodejs_version.as_str()),
+        ),
+    ];
+
+    fields
+        .iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| {
+            format!(
+                "{}: {} -> {}",
+                field,
+                old.unwrap_or("(none)"),
+                new.unwrap_or("(none)")
+            )
+        })
+        .collect()
+}
+
 /// Toggle a DDEV service on or off (e.g. xdebug, xhgui)
-/// Uses async command that waits for process exit directly, avoiding pipe-hang issues
+/// Waits for process exit directly instead of reading pipes to EOF, avoiding pipe-hang issues
 /// where subprocesses (like docker exec) inherit stdout/stderr file descriptors.
-/// Captures output and emits it via window events for terminal display.
+/// Output is streamed via window events and the child is registered so it can be cancelled;
+/// the process ID for cancelling is only sent in the `started` status event, since this
+/// resolves once the command has completed
 #[tauri::command]
 pub async fn toggle_service(
     window: Window,
-    _name: String,
+    name: String,
     approot: String,
     service: String,
     enable: bool,
 ) -> Result<(), DdevError> {
     let action = if enable { "on" } else { "off" };
+    let command_name = format!("toggle-{}", service);
+    let process_id = generate_process_id();
     let ddev_cmd = get_ddev_command();
     let enhanced_path = get_enhanced_path();
 
@@ -280,26 +2546,27 @@ pub async fn toggle_service(
     let _ = window.emit(
         "command-status",
         CommandStatus {
-            command: format!("toggle-{}", service),
-            project: _name.clone(),
+            command: command_name.clone(),
+            project: name.clone(),
             status: "started".to_string(),
             message: Some(format!("Running: ddev {} {}", service, action)),
-            process_id: None,
+            process_id: Some(process_id.clone()),
         },
     );
 
-    let output = AsyncCommand::new(&ddev_cmd)
+    let mut child = Command::new(&ddev_cmd)
         .args(&full_args)
         .current_dir(&approot)
         .env("PATH", &enhanced_path)
-        .output()
-        .await
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| {
             let _ = window.emit(
                 "command-status",
                 CommandStatus {
-                    command: format!("toggle-{}", service),
-                    project: _name.clone(),
+                    command: command_name.clone(),
+                    project: name.clone(),
                     status: "error".to_string(),
                     message: Some(format!("Failed to run ddev {} {}", service, action)),
                     process_id: None,
@@ -312,58 +2579,268 @@ pub async fn toggle_service(
             }
         })?;
 
-    // Emit captured stdout
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        let _ = window.emit(
-            "command-output",
-            CommandOutput {
-                line: line.to_string(),
-                stream: "stdout".to_string(),
-            },
-        );
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    // Stream output from detached reader threads; they're never joined because
+    // the pipes may stay open after ddev itself exits
+    if let Some(stdout) = stdout {
+        let window = window.clone();
+        thread::spawn(move || {
+            let reader = BufReader::new(stdout);
+            for line in reader.lines().map_while(Result::ok) {
+                let _ = window.emit("command-output", CommandOutput::new(line, "stdout"));
+            }
+        });
     }
 
-    // Emit captured stderr
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    for line in stderr.lines() {
-        let _ = window.emit(
-            "command-output",
-            CommandOutput {
-                line: line.to_string(),
-                stream: "stderr".to_string(),
-            },
-        );
+    if let Some(stderr) = stderr {
+        let window = window.clone();
+        thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            for line in reader.lines().map_while(Result::ok) {
+                let _ = window.emit("command-output", CommandOutput::new(line, "stderr"));
+            }
+        });
     }
 
-    if output.status.success() {
-        let _ = window.emit(
-            "command-status",
-            CommandStatus {
-                command: format!("toggle-{}", service),
-                project: _name,
-                status: "finished".to_string(),
-                message: Some(format!("ddev {} {} completed", service, action)),
-                process_id: None,
-            },
-        );
-        Ok(())
-    } else {
-        let _ = window.emit(
-            "command-status",
-            CommandStatus {
-                command: format!("toggle-{}", service),
-                project: _name,
-                status: "error".to_string(),
-                message: Some(format!("ddev {} {} failed", service, action)),
-                process_id: None,
-            },
-        );
-        Err(DdevError::CommandFailed(format!(
-            "ddev {} {} failed",
-            service, action
-        )))
+    register_child_process(&process_id, child, &command_name, &name);
+
+    match wait_for_registered_child(&process_id).await {
+        Some(true) => {
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name,
+                    project: name,
+                    status: "finished".to_string(),
+                    message: Some(format!("ddev {} {} completed", service, action)),
+                    process_id: None,
+                },
+            );
+            Ok(())
+        }
+        Some(false) => {
+            let _ = window.emit(
+                "command-status",
+                CommandStatus {
+                    command: command_name,
+                    project: name,
+                    status: "error".to_string(),
+                    message: Some(format!("ddev {} {} failed", service, action)),
+                    process_id: None,
+                },
+            );
+            Err(DdevError::CommandFailed(format!(
+                "ddev {} {} failed",
+                service, action
+            )))
+        }
+        None => {
+            // Cancelled - cancel_command already emitted the status
+            Err(DdevError::CommandFailed(format!(
+                "ddev {} {} was cancelled",
+                service, action
+            )))
+        }
+    }
+}
+
+/// Flip Xdebug to the opposite of its current runtime state
+/// Reads the state via `ddev xdebug status` so the UI doesn't need to compute the target,
+/// and returns the state observed after the toggle
+#[tauri::command]
+pub async fn toggle_xdebug(
+    window: Window,
+    name: String,
+    approot: String,
+) -> Result<XdebugState, DdevError> {
+    let _guard = XDEBUG_TOGGLE_LOCK.lock().await;
+
+    let currently_enabled = check_xdebug_runtime(&approot).await?;
+    toggle_service(
+        window,
+        name,
+        approot.clone(),
+        "xdebug".to_string(),
+        !currently_enabled,
+    )
+    .await?;
+
+    let enabled = check_xdebug_runtime(&approot)
+        .await
+        .unwrap_or(!currently_enabled);
+    Ok(XdebugState { enabled })
+}
+
+/// Port DDEV's Xdebug connects back to the IDE on (xdebug.client_port)
+const XDEBUG_PORT: u16 = 9003;
+
+/// Where the project root is mounted in the web container
+const CONTAINER_APPROOT: &str = "/var/www/html";
+
+/// Name of the VS Code launch configuration written by `generate_xdebug_ide_config`
+const VSCODE_XDEBUG_CONFIG_NAME: &str = "Listen for Xdebug (DDEV)";
+
+/// Write an IDE debug config so Xdebug works without manual setup
+/// `ide` is "vscode" (adds a PHP Debug launch config to `.vscode/launch.json`) or "phpstorm"
+/// (adds a PHP server with path mappings to `.idea/workspace.xml`; PhpStorm should be closed,
+/// since it rewrites that file on exit). Returns a summary of what was written.
+#[tauri::command]
+pub async fn generate_xdebug_ide_config(approot: String, ide: String) -> Result<String, DdevError> {
+    match ide.as_str() {
+        "vscode" => write_vscode_xdebug_config(&approot),
+        "phpstorm" => {
+            let details: DdevProjectDetails =
+                run_ddev_json_command_in_dir_async(&["describe"], &approot).await?;
+            write_phpstorm_xdebug_config(&approot, &details.hostname)
+        }
+        _ => Err(DdevError::CommandFailed(format!(
+            "Unsupported IDE: {} (expected vscode or phpstorm)",
+            ide
+        ))),
+    }
+}
+
+/// Add (or replace) the DDEV Xdebug listener in `.vscode/launch.json`
+fn write_vscode_xdebug_config(approot: &str) -> Result<String, DdevError> {
+    let vscode_dir = std::path::Path::new(approot).join(".vscode");
+    let path = vscode_dir.join("launch.json");
+
+    let mut launch: serde_json::Value = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            DdevError::ParseError(format!(
+                "Can't update .vscode/launch.json (comments or invalid JSON?): {}",
+                e
+            ))
+        })?,
+        Err(_) => serde_json::json!({ "version": "0.2.0", "configurations": [] }),
+    };
+
+    let config = serde_json::json!({
+        "name": VSCODE_XDEBUG_CONFIG_NAME,
+        "type": "php",
+        "request": "launch",
+        "hostname": "0.0.0.0",
+        "port": XDEBUG_PORT,
+        "pathMappings": { CONTAINER_APPROOT: "${workspaceFolder}" },
+    });
+
+    let Some(launch_object) = launch.as_object_mut() else {
+        return Err(DdevError::ParseError(
+            ".vscode/launch.json is not a JSON object".to_string(),
+        ));
+    };
+    let configurations = launch_object
+        .entry("configurations")
+        .or_insert_with(|| serde_json::json!([]));
+    let Some(configurations) = configurations.as_array_mut() else {
+        return Err(DdevError::ParseError(
+            "\"configurations\" in .vscode/launch.json is not a list".to_string(),
+        ));
+    };
+
+    let existing = configurations
+        .iter()
+        .position(|c| c.get("name").and_then(|n| n.as_str()) == Some(VSCODE_XDEBUG_CONFIG_NAME));
+    let action = match existing {
+        Some(index) => {
+            configurations[index] = config;
+            "Updated"
+        }
+        None => {
+            configurations.push(config);
+            "Added"
+        }
+    };
+
+    let content = serde_json::to_string_pretty(&launch)
+        .map_err(|e| DdevError::ParseError(format!("Failed to serialize launch.json: {}", e)))?;
+    std::fs::create_dir_all(&vscode_dir)
+        .map_err(|e| DdevError::IoError(format!("Failed to create .vscode: {}", e)))?;
+    write_atomic(&path, content + "\n")
+        .map_err(|e| DdevError::IoError(format!("Failed to write launch.json: {}", e)))?;
+
+    Ok(format!(
+        "{} \"{}\" in .vscode/launch.json (port {}, {} -> ${{workspaceFolder}}). \
+         Requires the PHP Debug extension.",
+        action, VSCODE_XDEBUG_CONFIG_NAME, XDEBUG_PORT, CONTAINER_APPROOT
+    ))
+}
+
+/// Add a PHP server for the project's hostname to `.idea/workspace.xml`
+/// DDEV sets `PHP_IDE_CONFIG=serverName=<hostname>`, so the server name must match it
+fn write_phpstorm_xdebug_config(approot: &str, hostname: &str) -> Result<String, DdevError> {
+    if hostname.is_empty() {
+        return Err(DdevError::CommandFailed(
+            "Project has no hostname".to_string(),
+        ));
     }
+
+    let idea_dir = std::path::Path::new(approot).join(".idea");
+    let path = idea_dir.join("workspace.xml");
+
+    let server = format!(
+        r#"      <server host="{host}" name="{host}" port="443" use_path_mappings="true">
+        <path_mappings>
+          <mapping local-root="$PROJECT_DIR$" remote-root="{remote}" />
+        </path_mappings>
+      </server>
+"#,
+        host = hostname,
+        remote = CONTAINER_APPROOT
+    );
+    let component = format!(
+        "  <component name=\"PhpServers\">\n    <servers>\n{}    </servers>\n  </component>\n",
+        server
+    );
+
+    let content = match std::fs::read_to_string(&path) {
+        Err(_) => format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<project version=\"4\">\n{}</project>\n",
+            component
+        ),
+        Ok(existing) if existing.contains(&format!("name=\"{}\"", hostname)) => {
+            return Ok(format!(
+                "PhpStorm already has a PHP server named {} in .idea/workspace.xml; nothing changed",
+                hostname
+            ));
+        }
+        Ok(existing) if existing.contains("<component name=\"PhpServers\">") => {
+            let Some(index) = existing.find("<servers>") else {
+                return Err(DdevError::ParseError(
+                    "Unexpected PhpServers layout in .idea/workspace.xml; add the server manually"
+                        .to_string(),
+                ));
+            };
+            let insert_at = index + "<servers>".len();
+            format!(
+                "{}\n{}{}",
+                &existing[..insert_at],
+                server.trim_end(),
+                &existing[insert_at..]
+            )
+        }
+        Ok(existing) => {
+            let Some(index) = existing.rfind("</project>") else {
+                return Err(DdevError::ParseError(
+                    ".idea/workspace.xml is not a PhpStorm project file".to_string(),
+                ));
+            };
+            format!("{}{}{}", &existing[..index], component, &existing[index..])
+        }
+    };
+
+    std::fs::create_dir_all(&idea_dir)
+        .map_err(|e| DdevError::IoError(format!("Failed to create .idea: {}", e)))?;
+    write_atomic(&path, content)
+        .map_err(|e| DdevError::IoError(format!("Failed to write workspace.xml: {}", e)))?;
+
+    Ok(format!(
+        "Added PHP server {} to .idea/workspace.xml ($PROJECT_DIR$ -> {}). \
+         Listen for debug connections on port {} in PhpStorm.",
+        hostname, CONTAINER_APPROOT, XDEBUG_PORT
+    ))
 }
 
 /// Change the PHP version for a DDEV project
@@ -381,8 +2858,7 @@ pub fn change_php_version(
         window,
         name,
         approot,
-        "php-version".to_string(),
-        php_version.clone(),
+        vec![("php-version".to_string(), php_version.clone())],
         "change-php",
         format!("PHP version changed to {} successfully", php_version),
         restart,
@@ -404,10 +2880,321 @@ pub fn change_nodejs_version(
         window,
         name,
         approot,
-        "nodejs-version".to_string(),
-        nodejs_version.clone(),
+        vec![("nodejs-version".to_string(), nodejs_version.clone())],
         "change-nodejs",
         format!("Node.js version changed to {} successfully", nodejs_version),
         restart,
     )
 }
+
+/// Set custom router HTTP/HTTPS ports for a project
+/// Emits a warning line if the ports clash with another running project
+/// Runs `ddev config --router-http-port=X --router-https-port=Y` and restarts by default
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub async fn set_router_ports(
+    window: Window,
+    name: String,
+    approot: String,
+    http_port: String,
+    https_port: String,
+    restart: Option<bool>,
+) -> Result<String, DdevError> {
+    let parse_port = |port: &str, label: &str| -> Result<u16, DdevError> {
+        port.trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|p| *p > 0)
+            .ok_or_else(|| {
+                DdevError::CommandFailed(format!(
+                    "Invalid {} port: {} (must be a number between 1 and 65535)",
+                    label, port
+                ))
+            })
+    };
+    let http = parse_port(&http_port, "HTTP")?;
+    let https = parse_port(&https_port, "HTTPS")?;
+
+    if http == https {
+        return Err(DdevError::CommandFailed(
+            "HTTP and HTTPS router ports must be different".to_string(),
+        ));
+    }
+
+    // Warn (but don't block) when another running project already claims one of the ports
+    for (port, owner) in find_port_owners(&name, http, https).await {
+        let _ = window.emit(
+            "command-output",
+            CommandOutput::new(
+                format!(
+                    "Warning: port {} is already used by project {}",
+                    port, owner
+                ),
+                "stderr",
+            ),
+        );
+    }
+
+    change_project_config(
+        window,
+        name,
+        approot,
+        vec![
+            ("router-http-port".to_string(), http.to_string()),
+            ("router-https-port".to_string(), https.to_string()),
+        ],
+        "change-router-ports",
+        format!("Router ports changed to {}/{} successfully", http, https),
+        restart.unwrap_or(true),
+    )
+}
+
+/// Set the timezone for a DDEV project
+/// Runs `ddev config --timezone=<tz>` and restarts by default, since PHP reads it on container start
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn set_timezone(
+    window: Window,
+    name: String,
+    approot: String,
+    timezone: String,
+    restart: Option<bool>,
+) -> Result<String, DdevError> {
+    let timezone = timezone.trim().to_string();
+    if !is_valid_timezone(&timezone) {
+        return Err(DdevError::CommandFailed(format!(
+            "Invalid timezone: {} (expected an IANA name like Europe/Lisbon)",
+            timezone
+        )));
+    }
+
+    change_project_config(
+        window,
+        name,
+        approot,
+        vec![("timezone".to_string(), timezone.clone())],
+        "change-timezone",
+        format!("Timezone changed to {} successfully", timezone),
+        restart.unwrap_or(true),
+    )
+}
+
+/// Composer channels accepted by `ddev config --composer-version`
+const COMPOSER_CHANNELS: &[&str] = &["1", "2", "2.2", "stable", "preview", "snapshot"];
+
+/// Set the Composer version for a DDEV project: a channel (1, 2, 2.2, stable, preview, snapshot)
+/// or an exact version like 2.7.7. Runs `ddev config --composer-version=<v>`
+/// DDEV installs the requested Composer when the web container starts, so the change only
+/// takes effect after a restart; `restart` defaults to false to let users batch changes
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn set_composer_version(
+    window: Window,
+    name: String,
+    approot: String,
+    version: String,
+    restart: Option<bool>,
+) -> Result<String, DdevError> {
+    let version = version.trim().to_string();
+    let valid =
+        COMPOSER_CHANNELS.contains(&version.as_str()) || semver::Version::parse(&version).is_ok();
+    if !valid {
+        return Err(DdevError::CommandFailed(format!(
+            "Invalid Composer version: {} (expected {} or an exact version like 2.7.7)",
+            version,
+            COMPOSER_CHANNELS.join(", ")
+        )));
+    }
+
+    change_project_config(
+        window,
+        name,
+        approot,
+        vec![("composer-version".to_string(), version.clone())],
+        "change-composer",
+        format!("Composer version set to {} successfully", version),
+        restart.unwrap_or(false),
+    )
+}
+
+/// Check a timezone has the IANA `Area/City` shape (or is UTC)
+/// Where the host has a zoneinfo database, the zone must also exist there
+fn is_valid_timezone(timezone: &str) -> bool {
+    let is_part = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+    };
+    if timezone == "UTC" {
+        return true;
+    }
+    let parts: Vec<&str> = timezone.split('/').collect();
+    if parts.len() < 2 || !parts.iter().all(|p| is_part(p)) {
+        return false;
+    }
+
+    let zoneinfo = std::path::Path::new("/usr/share/zoneinfo");
+    !zoneinfo.is_dir() || zoneinfo.join(timezone).is_file()
+}
+
+/// Kinds of value accepted by router-level config options
+enum RouterOptionValue {
+    Bool,
+    Port,
+    Hostnames,
+}
+
+/// Project config flags that only affect how the router exposes the project
+/// (HTTP/2 is a global router setting; see `set_global_flag("disable_http2", ...)`)
+const ROUTER_OPTIONS: &[(&str, RouterOptionValue)] = &[
+    ("use-dns-when-possible", RouterOptionValue::Bool),
+    ("bind-all-interfaces", RouterOptionValue::Bool),
+    ("mailpit-http-port", RouterOptionValue::Port),
+    ("mailpit-https-port", RouterOptionValue::Port),
+    ("project-tld", RouterOptionValue::Hostnames),
+    ("additional-hostnames", RouterOptionValue::Hostnames),
+    ("additional-fqdns", RouterOptionValue::Hostnames),
+];
+
+/// Set a router-level option for a project, limited to `ROUTER_OPTIONS`
+/// Supported: use-dns-when-possible, bind-all-interfaces (true/false), mailpit-http-port,
+/// mailpit-https-port (port numbers), project-tld, additional-hostnames, additional-fqdns
+/// (comma-separated hostnames). All take effect only after a restart.
+/// Returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn set_router_option(
+    window: Window,
+    name: String,
+    approot: String,
+    option: String,
+    value: String,
+    restart: bool,
+) -> Result<String, DdevError> {
+    let option = option.replace('_', "-");
+    let value = value.trim().to_string();
+    let (_, kind) = ROUTER_OPTIONS
+        .iter()
+        .find(|(key, _)| *key == option)
+        .ok_or_else(|| {
+            DdevError::CommandFailed(format!("Unsupported router option: {}", option))
+        })?;
+
+    let is_hostname = |host: &str| {
+        !host.is_empty()
+            && host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '*'))
+    };
+    let valid = match kind {
+        RouterOptionValue::Bool => value == "true" || value == "false",
+        RouterOptionValue::Port => value.parse::<u16>().is_ok_and(|port| port > 0),
+        // An empty list clears additional hostnames
+        RouterOptionValue::Hostnames => {
+            value.is_empty() || value.split(',').all(|host| is_hostname(host.trim()))
+        }
+    };
+    if !valid {
+        return Err(DdevError::CommandFailed(format!(
+            "Invalid value for {}: {}",
+            option, value
+        )));
+    }
+
+    change_project_config(
+        window,
+        name,
+        approot,
+        vec![(option.clone(), value.clone())],
+        "change-router-option",
+        format!("{} changed to {} successfully", option, value),
+        restart,
+    )
+}
+
+/// Find which other running projects already use the requested router ports
+/// Projects sharing the same router ports is normal (the router is shared), so only
+/// services' published host ports and cross-protocol router ports count as conflicts
+async fn find_port_owners(exclude_project: &str, http: u16, https: u16) -> Vec<(u16, String)> {
+    let projects: Vec<DdevProjectBasic> = match run_ddev_json_command_async(&["list"]).await {
+        Ok(projects) => projects,
+        Err(_) => return vec![],
+    };
+
+    let mut owners = Vec::new();
+    for project in projects
+        .iter()
+        .filter(|p| p.name != exclude_project && p.status == "running")
+    {
+        let details: DdevProjectDetails =
+            match run_ddev_json_command_async(&["describe", &project.name]).await {
+                Ok(details) => details,
+                Err(_) => continue,
+            };
+
+        let service_ports: Vec<String> = details
+            .services
+            .into_values()
+            .flat_map(|service| service.host_ports_mapping)
+            .map(|mapping| mapping.host_port)
+            .collect();
+        let uses = |port: u16, router_port_for_other_protocol: &Option<String>| {
+            let port = port.to_string();
+            service_ports.contains(&port) || router_port_for_other_protocol.as_ref() == Some(&port)
+        };
+
+        if uses(http, &details.router_https_port) {
+            owners.push((http, project.name.clone()));
+        }
+        if uses(https, &details.router_http_port) {
+            owners.push((https, project.name.clone()));
+        }
+    }
+
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(args: &[&str]) -> Result<String, DdevError> {
+        check_bulk_command(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn bulk_allows_project_scoped_commands() {
+        assert_eq!(bulk(&["xdebug", "on"]).unwrap(), "xdebug");
+        assert_eq!(bulk(&["--json-output", "describe"]).unwrap(), "describe");
+        assert_eq!(bulk(&["status"]).unwrap(), "describe");
+        assert_eq!(bulk(&["drush", "cr"]).unwrap(), "drush");
+        // Arguments after a passthrough command belong to the tool, not ddev
+        assert_eq!(bulk(&["exec", "ls", "-a"]).unwrap(), "exec");
+        assert_eq!(bulk(&["stop", "--omit-snapshot"]).unwrap(), "stop");
+    }
+
+    #[test]
+    fn bulk_rejects_destructive_commands() {
+        assert!(bulk(&[]).is_err());
+        assert!(bulk(&["--json-output"]).is_err());
+        assert!(bulk(&["delete", "-y"]).is_err());
+        assert!(bulk(&["--json-output", "delete", "-y"]).is_err());
+        assert!(bulk(&["-j", "clean", "--all"]).is_err());
+        assert!(bulk(&["poweroff"]).is_err());
+        assert!(bulk(&["powerdown"]).is_err());
+        assert!(bulk(&["custom-command"]).is_err());
+    }
+
+    #[test]
+    fn bulk_rejects_fleet_and_data_flags() {
+        assert!(bulk(&["stop", "--all"]).is_err());
+        assert!(bulk(&["stop", "-a"]).is_err());
+        assert!(bulk(&["stop", "-aO"]).is_err());
+        assert!(bulk(&["stop", "--all=true"]).is_err());
+        assert!(bulk(&["rm", "--remove-data"]).is_err());
+        assert!(bulk(&["restart", "-a"]).is_err());
+        assert!(bulk(&["snapshot", "--cleanup"]).is_err());
+        assert!(bulk(&["snapshot", "--all"]).is_err());
+        assert!(bulk(&["-a", "stop"]).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::path::Path;
+use tauri::{Emitter, Window};
+
+use crate::ddev::run_ddev_command_streaming_in_dir;
+use crate::error::DdevError;
+use crate::types::CommandOutput;
+
+/// List the hosting providers configured for a project (`.ddev/providers/<name>.yaml`)
+/// These are the names accepted by `ddev pull`/`ddev push`
+#[tauri::command]
+pub fn list_providers(approot: String) -> Result<Vec<String>, DdevError> {
+    let providers_dir = Path::new(&approot).join(".ddev").join("providers");
+    let entries = match std::fs::read_dir(&providers_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(DdevError::IoError(format!(
+                "Failed to read .ddev/providers: {}",
+                e
+            )))
+        }
+    };
+
+    let mut providers: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            file_name
+                .strip_suffix(".yaml")
+                .filter(|name| !name.is_empty() && !name.starts_with('.'))
+                .map(|name| name.to_string())
+        })
+        .collect();
+    providers.sort();
+
+    Ok(providers)
+}
+
+/// Check `provider` is configured for the project, with guidance when none are
+fn ensure_provider(approot: &str, provider: &str) -> Result<(), DdevError> {
+    let providers = list_providers(approot.to_string())?;
+    if providers.is_empty() {
+        return Err(DdevError::CommandFailed(
+            "No hosting providers configured. Add a provider file (e.g. pantheon.yaml, \
+             platform.yaml, acquia.yaml) to .ddev/providers; see \
+             https://ddev.readthedocs.io/en/stable/users/providers/"
+                .to_string(),
+        ));
+    }
+    if !providers.iter().any(|p| p == provider) {
+        return Err(DdevError::CommandFailed(format!(
+            "Unknown provider: {} (configured: {})",
+            provider,
+            providers.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Pull the database and/or files from a hosting provider with `ddev pull <provider>`
+/// Runs non-interactively (`-y`); returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn pull_from_provider(
+    window: Window,
+    name: String,
+    approot: String,
+    provider: String,
+    skip_files: bool,
+    skip_db: bool,
+) -> Result<String, DdevError> {
+    if skip_files && skip_db {
+        return Err(DdevError::CommandFailed(
+            "Nothing to pull: both files and database are skipped".to_string(),
+        ));
+    }
+    ensure_provider(&approot, &provider)?;
+
+    let mut args = vec!["pull", provider.as_str(), "-y"];
+    if skip_files {
+        args.push("--skip-files");
+    }
+    if skip_db {
+        args.push("--skip-db");
+    }
+
+    run_ddev_command_streaming_in_dir(window, "pull", &name, &args, &approot)
+}
+
+/// Push the local database and/or files to a hosting provider with `ddev push <provider>`
+/// This overwrites the remote environment, so `confirm` must be true (the UI asks the user
+/// first); a warning line is emitted as the push starts. Runs non-interactively (`-y`);
+/// returns a process ID that can be used to cancel the command
+#[tauri::command]
+pub fn push_to_provider(
+    window: Window,
+    name: String,
+    approot: String,
+    provider: String,
+    skip_files: bool,
+    skip_db: bool,
+    confirm: bool,
+) -> Result<String, DdevError> {
+    if !confirm {
+        return Err(DdevError::CommandFailed(
+            "Pushing overwrites the remote environment and must be confirmed".to_string(),
+        ));
+    }
+    if skip_files && skip_db {
+        return Err(DdevError::CommandFailed(
+            "Nothing to push: both files and database are skipped".to_string(),
+        ));
+    }
+    ensure_provider(&approot, &provider)?;
+
+    let mut args = vec!["push", provider.as_str(), "-y"];
+    if skip_files {
+        args.push("--skip-files");
+    }
+    if skip_db {
+        args.push("--skip-db");
+    }
+
+    // Warn before starting, so the warning precedes the push rather than following it
+    let targets = match (skip_db, skip_files) {
+        (false, false) => "database and files",
+        (false, true) => "database",
+        _ => "files",
+    };
+    let _ = window.emit(
+        "command-output",
+        CommandOutput::new(
+            format!(
+                "WARNING: overwriting the {} on {} with the local {}",
+                targets, provider, targets
+            ),
+            "stderr",
+        ),
+    );
+
+    run_ddev_command_streaming_in_dir(window, "push", &name, &args, &approot)
+}
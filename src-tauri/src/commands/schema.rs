@@ -1,5 +1,5 @@
 use crate::error::DdevError;
-use crate::schema::{fetch_schema, get_schema, DdevSchema};
+use crate::schema::{fetch_schema, get_schema, remove_cached_schema, DdevSchema};
 
 /// Get the DDEV schema (from cache or fetch)
 #[tauri::command]
@@ -12,3 +12,9 @@ pub async fn get_ddev_schema() -> Result<DdevSchema, DdevError> {
 pub async fn refresh_ddev_schema() -> Result<DdevSchema, DdevError> {
     fetch_schema().await
 }
+
+/// Delete the cached DDEV schema so it is re-fetched on next use
+#[tauri::command]
+pub fn clear_schema_cache() -> Result<(), DdevError> {
+    remove_cached_schema()
+}
@@ -1,10 +1,118 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
+use std::time::Duration;
 use tauri::{Emitter, Manager, Window};
 
 use crate::error::DdevError;
+use crate::settings::{load_settings, update_settings};
 use crate::types::ScreenshotStatus;
 
+/// Concurrent captures allowed when the user hasn't configured a limit
+const DEFAULT_SCREENSHOT_CONCURRENCY: usize = 2;
+
+/// Upper bound for the configurable limit; each capture is a full Chrome instance
+const MAX_SCREENSHOT_CONCURRENCY: usize = 8;
+
+// Cancellation flags for in-progress captures, keyed by project name
+static SCREENSHOT_TASKS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Removes a capture's cancellation flag when its thread exits, unless a newer capture replaced it
+struct ScreenshotTaskGuard {
+    project: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for ScreenshotTaskGuard {
+    fn drop(&mut self) {
+        let mut tasks = SCREENSHOT_TASKS.lock().unwrap();
+        if tasks
+            .get(&self.project)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &self.cancelled))
+        {
+            tasks.remove(&self.project);
+        }
+    }
+}
+
+// Number of captures currently holding a browser, signalled when one finishes
+static ACTIVE_CAPTURES: Lazy<(Mutex<usize>, Condvar)> =
+    Lazy::new(|| (Mutex::new(0), Condvar::new()));
+
+/// A slot in the capture limit, released on drop
+struct CapturePermit;
+
+impl Drop for CapturePermit {
+    fn drop(&mut self) {
+        let (active, slot_freed) = &*ACTIVE_CAPTURES;
+        *active.lock().unwrap() -= 1;
+        slot_freed.notify_all();
+    }
+}
+
+/// Wait until fewer than the configured number of captures are running
+/// Emits a "queued" status while waiting; returns None if cancelled in the queue
+fn acquire_capture_permit(
+    window: &Window,
+    project_name: &str,
+    cancelled: &AtomicBool,
+) -> Option<CapturePermit> {
+    let limit = load_settings()
+        .screenshot_concurrency
+        .unwrap_or(DEFAULT_SCREENSHOT_CONCURRENCY);
+    let (active, slot_freed) = &*ACTIVE_CAPTURES;
+    let mut active = active.lock().unwrap();
+    let mut queued = false;
+
+    while *active >= limit {
+        if !queued {
+            queued = true;
+            let _ = window.emit(
+                "screenshot-status",
+                ScreenshotStatus {
+                    project: project_name.to_string(),
+                    status: "queued".to_string(),
+                    path: None,
+                    message: Some("Waiting for other screenshots to finish...".to_string()),
+                },
+            );
+        }
+        // Wake periodically so a cancel while queued is noticed
+        active = slot_freed
+            .wait_timeout(active, Duration::from_millis(200))
+            .unwrap()
+            .0;
+        if cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+    }
+
+    *active += 1;
+    Some(CapturePermit)
+}
+
+/// If the capture was cancelled, emit the cancelled status and return true
+fn screenshot_cancelled(window: &Window, project_name: &str, cancelled: &AtomicBool) -> bool {
+    if !cancelled.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    let _ = window.emit(
+        "screenshot-status",
+        ScreenshotStatus {
+            project: project_name.to_string(),
+            status: "cancelled".to_string(),
+            path: None,
+            message: Some("Screenshot cancelled".to_string()),
+        },
+    );
+    true
+}
+
 /// Get the screenshots directory, creating it if necessary
 fn get_screenshots_dir(app: &tauri::AppHandle) -> Result<PathBuf, DdevError> {
     let data_dir = app
@@ -22,7 +130,34 @@ fn get_screenshots_dir(app: &tauri::AppHandle) -> Result<PathBuf, DdevError> {
     Ok(screenshots_dir)
 }
 
+/// Screenshot file name for a project: `{project}.png`, or `{project}-{label}.png` for a labelled page
+/// The label is reduced to letters, digits, `-` and `_` so it is safe in a file name
+fn screenshot_file_name(project_name: &str, label: Option<&str>) -> String {
+    let label = label
+        .map(|label| {
+            label
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect::<String>()
+                .trim_matches('-')
+                .to_string()
+        })
+        .filter(|label| !label.is_empty());
+
+    match label {
+        Some(label) => format!("{}-{}.png", project_name, label),
+        None => format!("{}.png", project_name),
+    }
+}
+
 /// Capture a screenshot of a project's website
+/// An optional `label` keeps it separate from the project's main thumbnail (e.g. "admin")
 /// This runs in a background thread and emits screenshot-status events
 #[tauri::command]
 pub fn capture_screenshot(
@@ -30,8 +165,20 @@ pub fn capture_screenshot(
     window: Window,
     project_name: String,
     url: String,
+    label: Option<String>,
 ) -> Result<(), DdevError> {
     let screenshots_dir = get_screenshots_dir(&app)?;
+    let file_name = screenshot_file_name(&project_name, label.as_deref());
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    SCREENSHOT_TASKS
+        .lock()
+        .unwrap()
+        .insert(project_name.clone(), cancelled.clone());
+    let guard = ScreenshotTaskGuard {
+        project: project_name.clone(),
+        cancelled: cancelled.clone(),
+    };
 
     // Emit started status
     let _ = window.emit(
@@ -48,6 +195,18 @@ pub fn capture_screenshot(
     thread::spawn(move || {
         use headless_chrome::{Browser, LaunchOptions};
 
+        let _guard = guard;
+        let is_cancelled = || screenshot_cancelled(&window, &project_name, &cancelled);
+
+        // Held until the browser is dropped at the end of this thread
+        let _permit = match acquire_capture_permit(&window, &project_name, &cancelled) {
+            Some(permit) => permit,
+            None => {
+                is_cancelled();
+                return;
+            }
+        };
+
         // Emit capturing status
         let _ = window.emit(
             "screenshot-status",
@@ -81,6 +240,10 @@ pub fn capture_screenshot(
             }
         };
 
+        if is_cancelled() {
+            return;
+        }
+
         let browser = match Browser::new(launch_options) {
             Ok(b) => b,
             Err(e) => {
@@ -113,6 +276,10 @@ pub fn capture_screenshot(
             }
         };
 
+        if is_cancelled() {
+            return;
+        }
+
         // Navigate to URL
         if let Err(e) = tab.navigate_to(&url) {
             let _ = window.emit(
@@ -141,8 +308,13 @@ pub fn capture_screenshot(
             return;
         }
 
-        // Additional delay for JavaScript rendering
-        thread::sleep(std::time::Duration::from_secs(2));
+        // Additional delay for JavaScript rendering, checking for cancellation meanwhile
+        for _ in 0..20 {
+            if is_cancelled() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
 
         // Capture screenshot
         let png_data = match tab.capture_screenshot(
@@ -166,8 +338,12 @@ pub fn capture_screenshot(
             }
         };
 
+        if is_cancelled() {
+            return;
+        }
+
         // Save to file
-        let screenshot_path = screenshots_dir.join(format!("{}.png", project_name));
+        let screenshot_path = screenshots_dir.join(&file_name);
         if let Err(e) = std::fs::write(&screenshot_path, png_data) {
             let _ = window.emit(
                 "screenshot-status",
@@ -196,14 +372,43 @@ pub fn capture_screenshot(
     Ok(())
 }
 
-/// Get the path to a project's screenshot if it exists
+/// Cancel a project's in-progress screenshot capture
+/// The capture stops at its next phase boundary and emits a "cancelled" screenshot-status
+/// Returns false if no capture was running
+#[tauri::command]
+pub fn cancel_screenshot(project: String) -> Result<bool, DdevError> {
+    match SCREENSHOT_TASKS.lock().unwrap().get(&project) {
+        Some(cancelled) => {
+            cancelled.store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Set how many screenshots may be captured at once (1-8); further captures queue
+#[tauri::command]
+pub fn set_screenshot_concurrency(limit: usize) -> Result<(), DdevError> {
+    if !(1..=MAX_SCREENSHOT_CONCURRENCY).contains(&limit) {
+        return Err(DdevError::CommandFailed(format!(
+            "Screenshot concurrency must be between 1 and {}",
+            MAX_SCREENSHOT_CONCURRENCY
+        )));
+    }
+
+    update_settings(|settings| settings.screenshot_concurrency = Some(limit))
+}
+
+/// Get the path to a project's (optionally labelled) screenshot if it exists
 #[tauri::command]
 pub fn get_screenshot_path(
     app: tauri::AppHandle,
     project_name: String,
+    label: Option<String>,
 ) -> Result<Option<String>, DdevError> {
     let screenshots_dir = get_screenshots_dir(&app)?;
-    let screenshot_path = screenshots_dir.join(format!("{}.png", project_name));
+    let screenshot_path =
+        screenshots_dir.join(screenshot_file_name(&project_name, label.as_deref()));
 
     if screenshot_path.exists() {
         Ok(Some(screenshot_path.to_string_lossy().to_string()))
@@ -217,12 +422,14 @@ pub fn get_screenshot_path(
 pub fn get_screenshot_data(
     app: tauri::AppHandle,
     project_name: String,
+    label: Option<String>,
 ) -> Result<Option<String>, DdevError> {
     use std::fs;
     use std::io::Read;
 
     let screenshots_dir = get_screenshots_dir(&app)?;
-    let screenshot_path = screenshots_dir.join(format!("{}.png", project_name));
+    let screenshot_path =
+        screenshots_dir.join(screenshot_file_name(&project_name, label.as_deref()));
 
     if screenshot_path.exists() {
         let mut file = fs::File::open(&screenshot_path)
@@ -267,11 +474,16 @@ pub fn get_screenshot_data(
     }
 }
 
-/// Delete a project's screenshot
+/// Delete a project's (optionally labelled) screenshot
 #[tauri::command]
-pub fn delete_screenshot(app: tauri::AppHandle, project_name: String) -> Result<(), DdevError> {
+pub fn delete_screenshot(
+    app: tauri::AppHandle,
+    project_name: String,
+    label: Option<String>,
+) -> Result<(), DdevError> {
     let screenshots_dir = get_screenshots_dir(&app)?;
-    let screenshot_path = screenshots_dir.join(format!("{}.png", project_name));
+    let screenshot_path =
+        screenshots_dir.join(screenshot_file_name(&project_name, label.as_deref()));
 
     if screenshot_path.exists() {
         std::fs::remove_file(&screenshot_path)
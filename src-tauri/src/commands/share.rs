@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Window};
+
+use crate::ddev::run_ddev_command_streaming_with_line_handler;
+use crate::error::DdevError;
+use crate::types::ShareUrl;
+
+/// Find an ngrok tunnel URL in a line of `ddev share` output, e.g. in ngrok's
+/// "started tunnel ... url=https://abcd-1234.ngrok-free.app" log line
+fn find_ngrok_url(line: &str) -> Option<&str> {
+    let start = line.find("https://")?;
+    let url = line[start..]
+        .split(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+        .next()?;
+    url.contains(".ngrok").then_some(url)
+}
+
+/// Share a project publicly through an ngrok tunnel (non-blocking)
+/// Runs until cancelled with `cancel_command`, which also stops ngrok. Emits `share-url`
+/// whenever a new public URL is reported.
+#[tauri::command]
+pub fn share_project(window: Window, name: String) -> Result<String, DdevError> {
+    let last_url: Mutex<Option<String>> = Mutex::new(None);
+    let emitter = window.clone();
+    let project = name.clone();
+
+    let on_line = Arc::new(move |line: &str| {
+        let Some(url) = find_ngrok_url(line) else {
+            return;
+        };
+        // ngrok repeats the URL in later status lines; only report changes
+        let mut last_url = last_url.lock().unwrap();
+        if last_url.as_deref() == Some(url) {
+            return;
+        }
+        *last_url = Some(url.to_string());
+        let _ = emitter.emit(
+            "share-url",
+            ShareUrl {
+                project: project.clone(),
+                url: url.to_string(),
+            },
+        );
+    });
+
+    run_ddev_command_streaming_with_line_handler(window, "share", &name, &["share", &name], on_line)
+}
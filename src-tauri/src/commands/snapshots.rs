@@ -1,14 +1,43 @@
-use tauri::Window;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, State, Window};
 
 use crate::ddev::{
-    run_ddev_command_async, run_ddev_command_streaming, run_ddev_command_streaming_in_dir,
+    run_ddev_command_async, run_ddev_command_streaming, run_ddev_command_streaming_in_dir_logged,
 };
 use crate::error::DdevError;
+use crate::types::{DdevJsonResponse, SnapshotCreated, SnapshotInfo};
 
-/// List snapshots for a project (async, returns JSON)
+/// Name prefix for snapshots taken by the scheduler, so pruning never touches manual ones
+const AUTO_SNAPSHOT_PREFIX: &str = "auto-";
+
+/// Number of automatic snapshots kept per project when no retention is given
+const DEFAULT_AUTO_SNAPSHOT_KEEP: usize = 5;
+
+/// Running auto-snapshot schedulers, keyed by project name
+#[derive(Default)]
+pub struct AutoSnapshotTasks(Mutex<HashMap<String, JoinHandle<()>>>);
+
+/// List snapshots for a project, newest first
 #[tauri::command]
-pub async fn list_snapshots(project: String) -> Result<String, DdevError> {
-    run_ddev_command_async(&["snapshot", "--list", "--json-output", &project]).await
+pub async fn list_snapshots(project: String) -> Result<Vec<SnapshotInfo>, DdevError> {
+    let output = run_ddev_command_async(&["snapshot", "--list", "--json-output", &project]).await?;
+
+    // DDEV keys the list by project name, with null for a project without snapshots
+    let Ok(response) = serde_json::from_str::<
+        DdevJsonResponse<HashMap<String, Option<Vec<SnapshotInfo>>>>,
+    >(&output) else {
+        // No "raw" field means there is nothing to list; DDEV returns just an info message
+        return Ok(vec![]);
+    };
+
+    let mut snapshots: Vec<SnapshotInfo> = response.raw.into_values().flatten().flatten().collect();
+    // RFC 3339 timestamps from the same machine sort chronologically as strings
+    snapshots.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(snapshots)
 }
 
 /// Create a snapshot for a project (streaming output)
@@ -31,6 +60,43 @@ pub fn create_snapshot(
     run_ddev_command_streaming(window, "snapshot", &project, &args_refs)
 }
 
+/// Path of the log holding the stderr of a project's most recent snapshot restore
+fn restore_log_path(project: &str) -> Result<PathBuf, DdevError> {
+    if project.is_empty() || project.contains(['/', '\\']) || project.contains("..") {
+        return Err(DdevError::CommandFailed(format!(
+            "Invalid project name: {}",
+            project
+        )));
+    }
+
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| DdevError::IoError("Could not determine app data directory".to_string()))?;
+    let log_dir = data_dir.join("ddev-manager").join("restore-logs");
+    std::fs::create_dir_all(&log_dir).map_err(|e| {
+        DdevError::IoError(format!("Failed to create restore log directory: {}", e))
+    })?;
+
+    Ok(log_dir.join(format!("{}.log", project)))
+}
+
+/// Run `ddev snapshot restore <target>` (a snapshot name or `--latest`) from the project
+/// directory, keeping its stderr in the project's restore log (replacing the previous one)
+fn run_snapshot_restore(
+    window: Window,
+    project: &str,
+    target: &str,
+    approot: &str,
+) -> Result<String, DdevError> {
+    run_ddev_command_streaming_in_dir_logged(
+        window,
+        "snapshot-restore",
+        project,
+        &["snapshot", "restore", target],
+        approot,
+        Some(restore_log_path(project)?),
+    )
+}
+
 /// Restore a snapshot for a project (streaming output)
 /// Must run from project directory since `ddev snapshot restore` doesn't accept project name
 #[tauri::command]
@@ -40,13 +106,32 @@ pub fn restore_snapshot(
     snapshot: String,
     approot: String,
 ) -> Result<String, DdevError> {
-    run_ddev_command_streaming_in_dir(
-        window,
-        "snapshot-restore",
-        &project,
-        &["snapshot", "restore", &snapshot],
-        &approot,
-    )
+    run_snapshot_restore(window, &project, &snapshot, &approot)
+}
+
+/// Restore a project's most recent snapshot (`ddev snapshot restore --latest`, streaming output)
+/// When the project has no snapshots DDEV's error is streamed and the command fails
+#[tauri::command]
+pub fn restore_latest_snapshot(
+    window: Window,
+    project: String,
+    approot: String,
+) -> Result<String, DdevError> {
+    run_snapshot_restore(window, &project, "--latest", &approot)
+}
+
+/// Get the stderr captured from a project's most recent snapshot restore
+/// Returns None if no restore has been run for the project from this app
+#[tauri::command]
+pub fn get_last_restore_log(project: String) -> Result<Option<String>, DdevError> {
+    match std::fs::read_to_string(restore_log_path(&project)?) {
+        Ok(log) => Ok(Some(log)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(DdevError::IoError(format!(
+            "Failed to read restore log: {}",
+            e
+        ))),
+    }
 }
 
 /// Delete a specific snapshot (streaming output)
@@ -74,3 +159,192 @@ pub fn cleanup_snapshots(window: Window, project: String) -> Result<String, Ddev
         &["snapshot", "--cleanup", "-y", &project],
     )
 }
+
+/// Restore a snapshot from an arbitrary location (e.g. copied from another machine)
+/// Accepts either a snapshot archive (`<name>-<dbtype>_<version>.gz`) or a directory containing
+/// exactly one. The archive is copied into `.ddev/db_snapshots` and restored by name.
+#[tauri::command]
+pub fn restore_snapshot_from_path(
+    window: Window,
+    project: String,
+    approot: String,
+    snapshot_path: String,
+) -> Result<String, DdevError> {
+    let archive = find_snapshot_archive(Path::new(&snapshot_path))?;
+
+    let file_name = archive
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Snapshot archives are named <snapshot>-<dbtype>_<version>.gz
+    let snapshot_name = file_name
+        .trim_end_matches(".gz")
+        .rsplit_once('-')
+        .map(|(name, _)| name.to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| {
+            DdevError::CommandFailed(format!(
+                "{} doesn't look like a DDEV snapshot (expected <name>-<dbtype>_<version>.gz)",
+                file_name
+            ))
+        })?;
+
+    let snapshots_dir = Path::new(&approot).join(".ddev").join("db_snapshots");
+    std::fs::create_dir_all(&snapshots_dir)
+        .map_err(|e| DdevError::IoError(format!("Failed to create snapshots dir: {}", e)))?;
+
+    let destination = snapshots_dir.join(&file_name);
+    if destination != archive {
+        std::fs::copy(&archive, &destination)
+            .map_err(|e| DdevError::IoError(format!("Failed to copy snapshot: {}", e)))?;
+    }
+
+    run_snapshot_restore(window, &project, &snapshot_name, &approot)
+}
+
+/// Locate the snapshot archive for a path that is either the archive or a directory holding it
+fn find_snapshot_archive(path: &Path) -> Result<PathBuf, DdevError> {
+    let is_gz = |p: &Path| p.is_file() && p.extension().is_some_and(|ext| ext == "gz");
+
+    if is_gz(path) {
+        return Ok(path.to_path_buf());
+    }
+
+    if !path.is_dir() {
+        return Err(DdevError::CommandFailed(format!(
+            "Snapshot not found: {}",
+            path.display()
+        )));
+    }
+
+    let archives: Vec<PathBuf> = std::fs::read_dir(path)
+        .map_err(|e| DdevError::IoError(format!("Failed to read snapshot dir: {}", e)))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| is_gz(p))
+        .collect();
+
+    match archives.as_slice() {
+        [archive] => Ok(archive.clone()),
+        [] => Err(DdevError::CommandFailed(format!(
+            "No snapshot archive (.gz) found in {}",
+            path.display()
+        ))),
+        _ => Err(DdevError::CommandFailed(format!(
+            "Multiple snapshot archives found in {}; select the one to restore",
+            path.display()
+        ))),
+    }
+}
+
+/// Start taking a snapshot of a project every `interval_minutes`, keeping only the latest `keep`
+/// Replaces any scheduler already running for the project
+#[tauri::command]
+pub fn start_auto_snapshot(
+    app: AppHandle,
+    tasks: State<'_, AutoSnapshotTasks>,
+    project: String,
+    approot: String,
+    interval_minutes: u64,
+    keep: Option<usize>,
+) -> Result<(), DdevError> {
+    if interval_minutes == 0 {
+        return Err(DdevError::CommandFailed(
+            "Snapshot interval must be at least 1 minute".to_string(),
+        ));
+    }
+    let keep = keep.unwrap_or(DEFAULT_AUTO_SNAPSHOT_KEEP).max(1);
+    let interval = Duration::from_secs(interval_minutes * 60);
+
+    let task_project = project.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+
+            // Stop quietly if the project was removed from disk
+            if !Path::new(&approot).join(".ddev").is_dir() {
+                break;
+            }
+
+            let snapshot = format!(
+                "{}{}",
+                AUTO_SNAPSHOT_PREFIX,
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default()
+            );
+            if run_ddev_command_async(&["snapshot", "--name", &snapshot, &task_project])
+                .await
+                .is_err()
+            {
+                // Usually the project is stopped; try again next interval
+                continue;
+            }
+
+            let pruned = prune_auto_snapshots(&task_project, keep).await;
+            let _ = app.emit(
+                "snapshot-created",
+                SnapshotCreated {
+                    project: task_project.clone(),
+                    snapshot,
+                    pruned,
+                },
+            );
+        }
+    });
+
+    let mut tasks = tasks.0.lock().unwrap();
+    if let Some(previous) = tasks.insert(project, handle) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+/// Stop the auto-snapshot scheduler for a project
+/// Returns false if no scheduler was running
+#[tauri::command]
+pub fn stop_auto_snapshot(
+    tasks: State<'_, AutoSnapshotTasks>,
+    project: String,
+) -> Result<bool, DdevError> {
+    match tasks.0.lock().unwrap().remove(&project) {
+        Some(handle) => {
+            handle.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Delete all but the newest `keep` automatic snapshots, returning the names removed
+async fn prune_auto_snapshots(project: &str, keep: usize) -> Vec<String> {
+    let Ok(listed) = list_snapshots(project.to_string()).await else {
+        return vec![];
+    };
+
+    // Auto snapshot names end in a unix timestamp, so they sort chronologically by it
+    let mut auto: Vec<(u64, String)> = listed
+        .into_iter()
+        .filter_map(|entry| {
+            let timestamp = entry
+                .name
+                .strip_prefix(AUTO_SNAPSHOT_PREFIX)?
+                .parse::<u64>()
+                .ok()?;
+            Some((timestamp, entry.name))
+        })
+        .collect();
+    auto.sort_unstable();
+
+    let excess = auto.len().saturating_sub(keep);
+    let mut pruned = Vec::new();
+    for (_, name) in auto.into_iter().take(excess) {
+        if run_ddev_command_async(&["snapshot", "--cleanup", "--name", &name, "-y", project])
+            .await
+            .is_ok()
+        {
+            pruned.push(name);
+        }
+    }
+    pruned
+}
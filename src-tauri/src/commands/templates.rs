@@ -0,0 +1,409 @@
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use tauri::{Emitter, Window};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::commands::{list_installed_addons, project_name_from_config};
+use crate::ddev::{
+    get_ddev_base_args, get_ddev_command, get_enhanced_path, run_ddev_command_streaming_in_dir,
+    run_ddev_json_command_in_dir_async, run_streaming_command,
+};
+use crate::error::DdevError;
+use crate::fs_utils::write_atomic;
+use crate::process::{
+    create_task_entry, generate_process_id, is_process_cancelled, remove_task_entry,
+};
+use crate::types::{
+    CommandOutput, CommandStatus, ConfigPreset, DdevProjectDetails, ProjectTemplateManifest,
+};
+
+/// Manifest file stored at the root of a project template bundle
+const TEMPLATE_MANIFEST: &str = "ddev-manager-template.json";
+
+/// Entries under `.ddev/` that are never bundled: generated by DDEV, machine-local or secret
+const TEMPLATE_EXCLUDED: &[&str] = &[
+    "db_snapshots",
+    "traefik",
+    "mutagen",
+    "import-db",
+    ".ssh",
+    ".backups",
+];
+
+/// Whether a path relative to `.ddev/` belongs in a template bundle
+fn is_template_file(relative: &Path) -> bool {
+    let mut components = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy());
+
+    // Top-level dot files/dirs are DDEV-generated (.ddev-docker-compose-*.yaml, .gitignore,
+    // .webimageBuild, ...) or local secrets (.env)
+    if components.next().is_none_or(|first| first.starts_with('.')) {
+        return false;
+    }
+
+    if relative
+        .components()
+        .any(|c| TEMPLATE_EXCLUDED.contains(&c.as_os_str().to_string_lossy().as_ref()))
+    {
+        return false;
+    }
+
+    let file_name = relative
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    !(file_name.ends_with(".local.yaml")
+        || file_name.ends_with(".pem")
+        || file_name.ends_with(".key"))
+}
+
+/// Collect files under `dir` (recursively) that belong in a template
+fn collect_template_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if !is_template_file(&relative) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_template_files(root, &path, files)?;
+        } else if path.is_file() {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Export a project's `.ddev/` config and installed add-ons as a portable zip bundle
+/// Generated files, volume data, local overrides and secrets are left out, and the project
+/// name is dropped from config.yaml so the bundle can be applied to any folder
+#[tauri::command]
+pub async fn export_project_template(approot: String, dest_zip: String) -> Result<(), DdevError> {
+    let ddev_dir = Path::new(&approot).join(".ddev");
+    if !ddev_dir.join("config.yaml").is_file() {
+        return Err(DdevError::CommandFailed(format!(
+            "No .ddev/config.yaml found in {} (not a DDEV project)",
+            approot
+        )));
+    }
+
+    let details: DdevProjectDetails =
+        run_ddev_json_command_in_dir_async(&["describe"], &approot).await?;
+    let addons = list_installed_addons(details.name.clone())
+        .await?
+        .into_iter()
+        .map(|addon| addon.repository)
+        .filter(|repository| !repository.is_empty())
+        .collect();
+
+    let manifest = ProjectTemplateManifest {
+        project_type: details.project_type,
+        php_version: details.php_version,
+        webserver_type: details.webserver_type,
+        database_type: details.database_type,
+        database_version: details.database_version,
+        nodejs_version: details.nodejs_version,
+        addons,
+    };
+
+    let mut files = Vec::new();
+    collect_template_files(&ddev_dir, &ddev_dir, &mut files)
+        .map_err(|e| DdevError::IoError(format!("Failed to read .ddev folder: {}", e)))?;
+
+    let write_err = |e: &dyn std::fmt::Display| {
+        DdevError::IoError(format!("Failed to write template bundle: {}", e))
+    };
+    let file = File::create(&dest_zip).map_err(|e| write_err(&e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| DdevError::ParseError(format!("Failed to serialize manifest: {}", e)))?;
+    zip.start_file(TEMPLATE_MANIFEST, options)
+        .map_err(|e| write_err(&e))?;
+    zip.write_all(manifest_json.as_bytes())
+        .map_err(|e| write_err(&e))?;
+
+    for relative in files {
+        let mut content = Vec::new();
+        File::open(ddev_dir.join(&relative))
+            .and_then(|mut f| f.read_to_end(&mut content))
+            .map_err(|e| {
+                DdevError::IoError(format!("Failed to read {}: {}", relative.display(), e))
+            })?;
+
+        if relative == Path::new("config.yaml") {
+            content = strip_project_name(&String::from_utf8_lossy(&content)).into_bytes();
+        }
+
+        // Zip entries always use forward slashes
+        let entry_name = Path::new(".ddev")
+            .join(&relative)
+            .to_string_lossy()
+            .replace('\\', "/");
+        zip.start_file(entry_name, options)
+            .map_err(|e| write_err(&e))?;
+        zip.write_all(&content).map_err(|e| write_err(&e))?;
+    }
+
+    zip.finish().map_err(|e| write_err(&e))?;
+    Ok(())
+}
+
+/// Remove the top-level `name:` key from a config.yaml so DDEV derives it from the folder
+fn strip_project_name(config: &str) -> String {
+    config
+        .lines()
+        .filter(|line| !line.starts_with("name:"))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Apply a template bundle to a folder: unpacks `.ddev/` and reinstalls the listed add-ons
+/// Refuses to overwrite an existing DDEV config. Add-on installs stream output and can be
+/// cancelled; returns a process ID
+#[tauri::command]
+pub fn apply_project_template(
+    window: Window,
+    target_approot: String,
+    src_zip: String,
+) -> Result<String, DdevError> {
+    let target = Path::new(&target_approot);
+    if !target.is_dir() {
+        return Err(DdevError::CommandFailed(format!(
+            "Target folder does not exist: {}",
+            target_approot
+        )));
+    }
+    if target.join(".ddev").join("config.yaml").exists() {
+        return Err(DdevError::CommandFailed(format!(
+            "{} already has a DDEV config; remove it before applying a template",
+            target_approot
+        )));
+    }
+
+    let read_err =
+        |e: &dyn std::fmt::Display| DdevError::IoError(format!("Failed to read template: {}", e));
+    let mut archive = ZipArchive::new(File::open(&src_zip).map_err(|e| read_err(&e))?)
+        .map_err(|e| read_err(&e))?;
+
+    let manifest: ProjectTemplateManifest = {
+        let mut entry = archive.by_name(TEMPLATE_MANIFEST).map_err(|_| {
+            DdevError::ParseError("Not a project template (no manifest)".to_string())
+        })?;
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .map_err(|e| read_err(&e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| DdevError::ParseError(format!("Invalid template manifest: {}", e)))?
+    };
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| read_err(&e))?;
+        // enclosed_name rejects absolute paths and `..` (zip-slip)
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        if !relative.starts_with(".ddev") || entry.is_dir() {
+            continue;
+        }
+
+        let destination = target.join(&relative);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| DdevError::IoError(format!("Failed to create folder: {}", e)))?;
+        }
+        let mut out = File::create(&destination).map_err(|e| {
+            DdevError::IoError(format!("Failed to write {}: {}", destination.display(), e))
+        })?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| {
+            DdevError::IoError(format!("Failed to write {}: {}", destination.display(), e))
+        })?;
+    }
+
+    install_template_addons(window, target_approot, manifest.addons)
+}
+
+/// Install a template's add-ons one after another (multi-step task)
+fn install_template_addons(
+    window: Window,
+    approot: String,
+    addons: Vec<String>,
+) -> Result<String, DdevError> {
+    let process_id = generate_process_id();
+    let command_name = "apply-template".to_string();
+    let project_name = Path::new(&approot)
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let process_id_clone = process_id.clone();
+
+    create_task_entry(&process_id, &command_name, &project_name);
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project_name.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Applying template ({} add-ons)", addons.len())),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    thread::spawn(move || {
+        let ddev_cmd = get_ddev_command();
+        let enhanced_path = get_enhanced_path();
+
+        for addon in &addons {
+            if is_process_cancelled(&process_id_clone) {
+                return;
+            }
+
+            let _ = window.emit(
+                "command-output",
+                CommandOutput::new(format!("Installing add-on {}...", addon), "stdout"),
+            );
+
+            let mut args = get_ddev_base_args();
+            args.extend_from_slice(&["add-on", "get", addon]);
+
+            match run_streaming_command(
+                &window,
+                &ddev_cmd,
+                &args,
+                &approot,
+                &enhanced_path,
+                Some(&process_id_clone),
+                &command_name,
+                &project_name,
+            ) {
+                Ok(true) => {}
+                Ok(false) => {
+                    remove_task_entry(&process_id_clone);
+                    let _ = window.emit(
+                        "command-status",
+                        CommandStatus {
+                            command: command_name,
+                            project: project_name,
+                            status: "error".to_string(),
+                            message: Some(format!("Failed to install add-on {}", addon)),
+                            process_id: None,
+                        },
+                    );
+                    return;
+                }
+                // Cancelled - cancel_command already emitted the status
+                Err(_) => return,
+            }
+        }
+
+        remove_task_entry(&process_id_clone);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project: project_name,
+                status: "finished".to_string(),
+                message: Some("Template applied".to_string()),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
+
+/// Preset name from a snippet file name (`php83.yaml` -> `php83`), if usable in
+/// `config.<name>.yaml`
+fn preset_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_string_lossy();
+    let name = file_name
+        .strip_suffix(".yaml")
+        .or_else(|| file_name.strip_suffix(".yml"))?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| name.to_string())
+}
+
+/// List the config presets (`*.yaml` DDEV config snippets) in a shared directory
+#[tauri::command]
+pub fn list_config_presets(dir: String) -> Result<Vec<ConfigPreset>, DdevError> {
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| DdevError::IoError(format!("Failed to read {}: {}", dir, e)))?;
+
+    let mut presets: Vec<ConfigPreset> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            preset_name(&path).map(|name| ConfigPreset {
+                name,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(presets)
+}
+
+/// Apply a config preset by copying it to `.ddev/config.<name>.yaml`, an override file DDEV
+/// merges over config.yaml. The snippet must be a YAML mapping. With `restart`, the project
+/// is restarted to pick it up and the process ID of the restart is returned.
+#[tauri::command]
+pub fn apply_config_preset(
+    window: Window,
+    approot: String,
+    preset_path: String,
+    restart: bool,
+) -> Result<Option<String>, DdevError> {
+    let ddev_dir = Path::new(&approot).join(".ddev");
+    if !ddev_dir.join("config.yaml").is_file() {
+        return Err(DdevError::CommandFailed(format!(
+            "No .ddev/config.yaml found in {} (not a DDEV project)",
+            approot
+        )));
+    }
+
+    let source = Path::new(&preset_path);
+    let name = preset_name(source).ok_or_else(|| {
+        DdevError::CommandFailed(format!(
+            "Preset file names must be <name>.yaml using letters, digits, - or _: {}",
+            preset_path
+        ))
+    })?;
+    let content = fs::read_to_string(source)
+        .map_err(|e| DdevError::IoError(format!("Failed to read preset: {}", e)))?;
+    let parsed: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| DdevError::ParseError(format!("Preset {} is not valid YAML: {}", name, e)))?;
+    if !parsed.is_mapping() {
+        return Err(DdevError::ParseError(format!(
+            "Preset {} must be a mapping of DDEV config keys",
+            name
+        )));
+    }
+
+    let target = ddev_dir.join(format!("config.{}.yaml", name));
+    write_atomic(&target, content)
+        .map_err(|e| DdevError::IoError(format!("Failed to write {}: {}", target.display(), e)))?;
+
+    if !restart {
+        return Ok(None);
+    }
+
+    let project_name = project_name_from_config(&approot);
+    run_ddev_command_streaming_in_dir(window, "restart", &project_name, &["restart"], &approot)
+        .map(Some)
+}
@@ -0,0 +1,89 @@
+use tauri::{Monitor, PhysicalPosition, PhysicalSize, WebviewWindow};
+
+use crate::error::DdevError;
+use crate::settings::{load_settings, update_settings, WindowState};
+
+/// How much of the window's top edge (title bar) must be on a monitor to restore there
+const MIN_VISIBLE_PX: i32 = 80;
+
+/// Height of the strip at the window's top that must stay reachable (the title bar)
+const TITLE_BAR_PX: i32 = 32;
+
+/// Smallest window size worth restoring; anything smaller is a leftover from a glitch
+const MIN_WINDOW_SIZE: u32 = 200;
+
+/// Whether enough of the window's title bar would be on `monitor` to grab and move it
+fn title_bar_on_monitor(state: &WindowState, monitor: &Monitor) -> bool {
+    let origin = monitor.position();
+    let size = monitor.size();
+    let (left, top) = (origin.x, origin.y);
+    let right = left.saturating_add(size.width as i32);
+    let bottom = top.saturating_add(size.height as i32);
+
+    let window_right = state.x.saturating_add(state.width as i32);
+    let overlap = window_right.min(right) - state.x.max(left);
+
+    overlap >= MIN_VISIBLE_PX && state.y >= top && state.y + TITLE_BAR_PX <= bottom
+}
+
+/// Save the main window's size, position and maximized state to settings
+/// While maximized only the flag is updated, keeping the last normal bounds.
+#[tauri::command]
+pub fn save_window_state(window: WebviewWindow) -> Result<(), DdevError> {
+    let failed =
+        |e: tauri::Error| DdevError::CommandFailed(format!("Failed to read window state: {}", e));
+    let maximized = window.is_maximized().map_err(failed)?;
+
+    let state = if maximized {
+        WindowState {
+            maximized: true,
+            ..load_settings().window_state.unwrap_or_default()
+        }
+    } else {
+        let position = window.outer_position().map_err(failed)?;
+        let size = window.inner_size().map_err(failed)?;
+        WindowState {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized: false,
+        }
+    };
+
+    update_settings(|settings| settings.window_state = Some(state))
+}
+
+/// Restore the main window geometry saved by `save_window_state`
+/// The position is only restored if the title bar lands on a connected monitor (so a window
+/// last shown on an unplugged display doesn't open off-screen); the size is capped to that
+/// monitor. Returns whether a saved state was applied.
+#[tauri::command]
+pub fn restore_window_state(window: WebviewWindow) -> Result<bool, DdevError> {
+    let Some(state) = load_settings().window_state else {
+        return Ok(false);
+    };
+    let failed =
+        |e: tauri::Error| DdevError::CommandFailed(format!("Failed to restore window: {}", e));
+
+    if state.width >= MIN_WINDOW_SIZE && state.height >= MIN_WINDOW_SIZE {
+        let monitors = window.available_monitors().map_err(failed)?;
+        if let Some(monitor) = monitors.iter().find(|m| title_bar_on_monitor(&state, m)) {
+            let bounds = monitor.size();
+            window
+                .set_size(PhysicalSize::new(
+                    state.width.min(bounds.width),
+                    state.height.min(bounds.height),
+                ))
+                .map_err(failed)?;
+            window
+                .set_position(PhysicalPosition::new(state.x, state.y))
+                .map_err(failed)?;
+        }
+    }
+
+    if state.maximized {
+        window.maximize().map_err(failed)?;
+    }
+    Ok(true)
+}
@@ -1,9 +1,14 @@
+#[cfg(target_os = "windows")]
+use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::collections::VecDeque;
 use std::env;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use tauri::{Emitter, Window};
 use tokio::process::Command as AsyncCommand;
 
@@ -12,7 +17,7 @@ use crate::process::{
     generate_process_id, is_process_cancelled, register_child_process, take_child_process,
     PROCESS_REGISTRY,
 };
-use crate::types::{CommandOutput, CommandStatus, DdevJsonResponse};
+use crate::types::{CommandOutput, CommandStatus, DdevJsonResponse, NeedsSudo};
 
 /// Common paths where DDEV might be installed
 /// macOS app bundles don't inherit shell PATH, so we need to search common locations
@@ -55,6 +60,32 @@ pub fn get_common_paths() -> Vec<String> {
     paths
 }
 
+// WSL distro DDEV runs in (None = the default distro), from the `wsl_distro` setting.
+// Leaked so it can be used in the `&'static str` base args; it only changes on user request.
+#[cfg(target_os = "windows")]
+static WSL_DISTRO: Lazy<Mutex<Option<&'static str>>> =
+    Lazy::new(|| Mutex::new(crate::settings::load_settings().wsl_distro.map(leak_str)));
+
+#[cfg(target_os = "windows")]
+fn leak_str(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// Switch the WSL distro used to run DDEV (None = the default distro)
+#[cfg(target_os = "windows")]
+pub fn set_active_wsl_distro(distro: Option<String>) {
+    *WSL_DISTRO.lock().unwrap() = distro.map(leak_str);
+}
+
+/// `wsl` arguments selecting the configured distro (empty for the default distro)
+#[cfg(target_os = "windows")]
+fn wsl_distro_args() -> Vec<&'static str> {
+    match *WSL_DISTRO.lock().unwrap() {
+        Some(distro) => vec!["-d", distro],
+        None => vec![],
+    }
+}
+
 /// Check if DDEV is available via WSL (Windows Subsystem for Linux)
 #[cfg(target_os = "windows")]
 pub fn check_wsl_ddev() -> bool {
@@ -62,6 +93,7 @@ pub fn check_wsl_ddev() -> bool {
 
     // Try to run ddev version through WSL
     Command::new("wsl")
+        .args(wsl_distro_args())
         .args(["ddev", "version"])
         .output()
         .map(|output| output.status.success())
@@ -110,7 +142,8 @@ pub fn find_ddev_path() -> Option<PathBuf> {
 }
 
 /// Determines how to run DDEV on Windows
-/// Returns ("wsl", ["ddev"]) for WSL or ("ddev", []) for native
+/// Returns ("wsl", ["ddev"]) for WSL (with `-d <distro>` first if one is configured)
+/// or ("ddev", []) for native
 #[cfg(target_os = "windows")]
 pub fn get_windows_ddev_execution() -> (&'static str, Vec<&'static str>) {
     // First check for native Windows DDEV
@@ -120,7 +153,9 @@ pub fn get_windows_ddev_execution() -> (&'static str, Vec<&'static str>) {
 
     // Check if DDEV is available through WSL
     if check_wsl_ddev() {
-        return ("wsl", vec!["ddev"]);
+        let mut args = wsl_distro_args();
+        args.push("ddev");
+        return ("wsl", args);
     }
 
     // Fall back to native ddev (will fail if not installed)
@@ -156,6 +191,27 @@ pub fn get_enhanced_path() -> String {
     paths.join(";")
 }
 
+/// Resolve an executable to its absolute path by searching a PATH-style string
+pub fn find_executable_in_path(name: &str, path: &str) -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let (separator, candidates) = (
+        ';',
+        vec![
+            format!("{}.exe", name),
+            format!("{}.cmd", name),
+            format!("{}.bat", name),
+            name.to_string(),
+        ],
+    );
+    #[cfg(not(target_os = "windows"))]
+    let (separator, candidates) = (':', vec![name.to_string()]);
+
+    path.split(separator)
+        .filter(|dir| !dir.is_empty())
+        .flat_map(|dir| candidates.iter().map(move |c| PathBuf::from(dir).join(c)))
+        .find(|candidate| candidate.is_file())
+}
+
 /// Get the DDEV command - either the full path or just "ddev"
 #[cfg(not(target_os = "windows"))]
 pub fn get_ddev_command() -> String {
@@ -186,11 +242,14 @@ fn is_using_wsl() -> bool {
     find_ddev_path().is_none() && check_wsl_ddev()
 }
 
-/// Get the base arguments for DDEV command (empty on Unix, ["ddev"] on Windows with WSL)
+/// Get the base arguments for DDEV command (empty on Unix, ["ddev"] on Windows with WSL,
+/// preceded by `-d <distro>` when a WSL distro is configured)
 #[cfg(target_os = "windows")]
 pub fn get_ddev_base_args() -> Vec<&'static str> {
     if is_using_wsl() {
-        vec!["ddev"]
+        let mut args = wsl_distro_args();
+        args.push("ddev");
+        args
     } else {
         vec![]
     }
@@ -203,6 +262,19 @@ pub fn get_ddev_base_args() -> Vec<&'static str> {
 
 /// Run a DDEV command and return the raw output (async version)
 pub async fn run_ddev_command_async(args: &[&str]) -> Result<String, DdevError> {
+    run_ddev_command_async_impl(args, None).await
+}
+
+/// Run a DDEV command from a project directory and return the raw output (async version)
+/// For commands like `ddev describe` or `ddev mysql` that act on the current project
+pub async fn run_ddev_command_in_dir_async(args: &[&str], dir: &str) -> Result<String, DdevError> {
+    run_ddev_command_async_impl(args, Some(dir)).await
+}
+
+async fn run_ddev_command_async_impl(
+    args: &[&str],
+    dir: Option<&str>,
+) -> Result<String, DdevError> {
     let ddev_cmd = get_ddev_command();
     let enhanced_path = get_enhanced_path();
 
@@ -211,18 +283,104 @@ pub async fn run_ddev_command_async(args: &[&str]) -> Result<String, DdevError>
     let mut full_args: Vec<&str> = base_args.clone();
     full_args.extend_from_slice(args);
 
-    let output = AsyncCommand::new(&ddev_cmd)
-        .args(&full_args)
+    let mut cmd = AsyncCommand::new(&ddev_cmd);
+    // Callers may give up on the command (e.g. `refresh_projects` timing out); dropping the
+    // future must not leave ddev running in the background
+    cmd.args(&full_args)
         .env("PATH", &enhanced_path)
+        .kill_on_drop(true);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd.output().await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            DdevError::NotInstalled
+        } else {
+            DdevError::IoError(e.to_string())
+        }
+    })?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_docker_unavailable_message(&stderr)
+            || is_docker_unavailable_message(&String::from_utf8_lossy(&output.stdout))
+        {
+            return Err(DdevError::DockerNotRunning);
+        }
+        Err(DdevError::CommandFailed(stderr.to_string()))
+    }
+}
+
+/// Whether DDEV output says it couldn't reach the Docker daemon
+fn is_docker_unavailable_message(output: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "cannot connect to the docker daemon",
+        "could not connect to a docker provider",
+        "is the docker daemon running",
+        "docker is not running",
+        "error during connect",
+    ];
+    let output = output.to_lowercase();
+    MARKERS.iter().any(|marker| output.contains(marker))
+}
+
+/// Number of trailing output lines kept for `StreamingOutcome::tail`
+const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Lines in a failure summary
+const ERROR_SUMMARY_LINES: usize = 3;
+
+/// Outcome of a completed (not cancelled) streaming command
+pub struct StreamingOutcome {
+    pub success: bool,
+    pub elapsed: Duration,
+    /// Last lines of output (all streams), oldest first
+    pub tail: Vec<String>,
+}
+
+impl StreamingOutcome {
+    /// Short reason for a failure: the last error-looking lines of output, or else the last
+    /// lines overall. None for a successful command or one without output.
+    pub fn error_summary(&self) -> Option<String> {
+        if self.success {
+            return None;
+        }
+
+        let errors: Vec<&str> = self
+            .tail
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| {
+                let lower = line.to_lowercase();
+                lower.contains("error") || lower.contains("failed")
+            })
+            .collect();
+        let lines: Vec<&str> = if errors.is_empty() {
+            self.tail
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect()
+        } else {
+            errors
+        };
+
+        let summary = lines[lines.len().saturating_sub(ERROR_SUMMARY_LINES)..].join("\n");
+        (!summary.is_empty()).then_some(summary)
+    }
+}
+
+/// Run a Docker CLI command and return the raw output (async version)
+pub async fn run_docker_command_async(args: &[&str]) -> Result<String, DdevError> {
+    let output = AsyncCommand::new("docker")
+        .args(args)
+        .env("PATH", get_enhanced_path())
         .output()
         .await
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                DdevError::NotInstalled
-            } else {
-                DdevError::IoError(e.to_string())
-            }
-        })?;
+        .map_err(|e| DdevError::IoError(format!("Failed to run docker: {}", e)))?;
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
@@ -232,6 +390,164 @@ pub async fn run_ddev_command_async(args: &[&str]) -> Result<String, DdevError>
     }
 }
 
+/// Output (lowercased) from sudo when it needs a password; spawned commands have no terminal
+/// to type it in, so they fail or hang at this point
+const SUDO_PROMPT_PATTERNS: &[&str] = &[
+    "[sudo] password for",
+    "sudo: a terminal is required to read the password",
+    "sudo: no tty present and no askpass program specified",
+    "sudo: a password is required",
+];
+
+/// Read output split on both "\n" and "\r", calling `on_segment` with each piece as it arrives
+/// Progress bars (docker pulls, composer) redraw their line with a bare "\r"; such segments are
+/// passed with `is_progress` set, meaning the next segment replaces them. "\r\n" is a newline.
+pub(crate) fn read_segments(reader: impl Read, mut on_segment: impl FnMut(String, bool)) {
+    let mut reader = BufReader::new(reader);
+    let mut segment = Vec::new();
+    // Saw "\r" and need the next byte to tell a progress redraw from "\r\n"
+    let mut after_cr = false;
+    let take =
+        |segment: &mut Vec<u8>| String::from_utf8_lossy(&std::mem::take(segment)).into_owned();
+
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+
+        for &byte in buf {
+            if after_cr {
+                after_cr = false;
+                if byte == b'\n' {
+                    on_segment(take(&mut segment), false);
+                    continue;
+                }
+                if !segment.is_empty() {
+                    on_segment(take(&mut segment), true);
+                }
+            }
+            match byte {
+                b'\r' => after_cr = true,
+                b'\n' => on_segment(take(&mut segment), false),
+                _ => segment.push(byte),
+            }
+        }
+
+        let consumed = buf.len();
+        reader.consume(consumed);
+    }
+
+    if after_cr || !segment.is_empty() {
+        on_segment(take(&mut segment), false);
+    }
+}
+
+/// Called with each output line of a streaming command before it is emitted
+pub type LineHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Emits command output lines, watching for sudo password prompts
+/// A prompt also emits a `needs-sudo` event telling the user to run the command in a terminal
+#[derive(Clone)]
+struct OutputEmitter {
+    window: Window,
+    command: String,
+    project: String,
+    command_line: String,
+    // Optional file that stderr lines are also written to
+    stderr_log: Option<Arc<Mutex<std::fs::File>>>,
+    // Last OUTPUT_TAIL_LINES lines across all streams
+    tail: Arc<Mutex<VecDeque<String>>>,
+    on_line: Option<LineHandler>,
+}
+
+impl OutputEmitter {
+    fn new(window: &Window, command: &str, project: &str, command_line: String) -> Self {
+        Self {
+            window: window.clone(),
+            command: command.to_string(),
+            project: project.to_string(),
+            command_line,
+            stderr_log: None,
+            tail: Arc::new(Mutex::new(VecDeque::with_capacity(OUTPUT_TAIL_LINES))),
+            on_line: None,
+        }
+    }
+
+    /// Last lines emitted so far, oldest first
+    fn tail(&self) -> Vec<String> {
+        self.tail.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Also write stderr lines to `file`, after a header naming the command
+    fn with_stderr_log(mut self, mut file: std::fs::File) -> Self {
+        let _ = writeln!(file, "$ {}", self.command_line);
+        self.stderr_log = Some(Arc::new(Mutex::new(file)));
+        self
+    }
+
+    fn with_line_handler(mut self, on_line: Option<LineHandler>) -> Self {
+        self.on_line = on_line;
+        self
+    }
+
+    /// Read a stream to its end, emitting each line as it arrives (see `read_segments`)
+    fn emit_segments(&self, reader: impl Read, stream: &str) {
+        read_segments(reader, |segment, is_progress| {
+            self.emit(segment, stream, is_progress)
+        });
+    }
+
+    fn emit(&self, line: String, stream: &str, is_progress: bool) {
+        // Number the line before any other work so the sequence follows read order
+        let mut output = CommandOutput::new(line, stream);
+        output.is_progress = is_progress;
+        let line = &output.line;
+
+        if let Some(on_line) = &self.on_line {
+            on_line(line);
+        }
+
+        // Progress redraws are transient, so only finished lines are logged and kept
+        if let Some(log) = self
+            .stderr_log
+            .as_ref()
+            .filter(|_| stream == "stderr" && !is_progress)
+        {
+            let _ = writeln!(log.lock().unwrap(), "{}", line);
+        }
+
+        if !is_progress {
+            let mut tail = self.tail.lock().unwrap();
+            if tail.len() == OUTPUT_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line.clone());
+        }
+
+        let lower = line.to_lowercase();
+        if SUDO_PROMPT_PATTERNS.iter().any(|p| lower.contains(p)) {
+            let _ = self.window.emit(
+                "needs-sudo",
+                NeedsSudo {
+                    command: self.command.clone(),
+                    project: self.project.clone(),
+                    line: line.clone(),
+                    guidance: format!(
+                        "This step needs administrator rights. Run it in a terminal, where sudo \
+                         can ask for your password: {}",
+                        self.command_line
+                    ),
+                },
+            );
+        }
+
+        let _ = self.window.emit("command-output", output);
+    }
+}
+
 /// Run a DDEV command with streaming output to the frontend (non-blocking)
 /// Returns a process ID that can be used to cancel the command
 pub fn run_ddev_command_streaming(
@@ -240,6 +556,124 @@ pub fn run_ddev_command_streaming(
     project_name: &str,
     args: &[&str],
 ) -> Result<String, DdevError> {
+    run_ddev_command_streaming_with_callback(
+        window,
+        command_name,
+        project_name,
+        args,
+        false,
+        |_| {},
+    )
+}
+
+/// Set up a command's output pipes. With `merge_output`, stdout and stderr share a single pipe
+/// (like `2>&1`) and its read end is returned: separate pipes are read on separate threads,
+/// which loses the relative order of lines. Merged lines are all reported as "stdout".
+fn configure_output(
+    command: &mut Command,
+    merge_output: bool,
+) -> std::io::Result<Option<std::io::PipeReader>> {
+    if !merge_output {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        return Ok(None);
+    }
+
+    let (reader, writer) = std::io::pipe()?;
+    command.stdout(writer.try_clone()?).stderr(writer);
+    Ok(Some(reader))
+}
+
+/// Run a DDEV command with streaming output, calling `on_complete` when it exits
+/// The callback runs before the final status event and is skipped if the command is cancelled
+/// `merge_output` keeps stdout and stderr lines in their original order (see `configure_output`)
+pub fn run_ddev_command_streaming_with_callback<F>(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    args: &[&str],
+    merge_output: bool,
+    on_complete: F,
+) -> Result<String, DdevError>
+where
+    F: FnOnce(StreamingOutcome) + Send + 'static,
+{
+    spawn_ddev_streaming(
+        window,
+        command_name,
+        project_name,
+        args,
+        merge_output,
+        false,
+        None,
+        on_complete,
+    )
+}
+
+/// Like `run_ddev_command_streaming_with_callback`, but with stdin kept open so the UI can
+/// answer prompts with `send_stdin`
+/// Only for commands that may ask for confirmation (e.g. addon installs): with no answer,
+/// a prompt waits until the command is cancelled instead of failing on end of input
+pub fn run_ddev_command_streaming_with_input<F>(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    args: &[&str],
+    on_complete: F,
+) -> Result<String, DdevError>
+where
+    F: FnOnce(StreamingOutcome) + Send + 'static,
+{
+    spawn_ddev_streaming(
+        window,
+        command_name,
+        project_name,
+        args,
+        false,
+        true,
+        None,
+        on_complete,
+    )
+}
+
+/// Run a long-lived DDEV command with streaming output, passing each line to `on_line`
+/// The command runs in its own process group (on Unix), so cancelling it also stops the
+/// helpers it starts, like the ngrok tunnel behind `ddev share`
+pub fn run_ddev_command_streaming_with_line_handler(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    args: &[&str],
+    on_line: LineHandler,
+) -> Result<String, DdevError> {
+    spawn_ddev_streaming(
+        window,
+        command_name,
+        project_name,
+        args,
+        true,
+        false,
+        Some(on_line),
+        |_| {},
+    )
+}
+
+/// Shared implementation of the background streaming runners above
+/// `accept_input` pipes stdin for `send_stdin`; a line handler marks a long-lived command
+/// that gets its own process group
+#[allow(clippy::too_many_arguments)]
+fn spawn_ddev_streaming<F>(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    args: &[&str],
+    merge_output: bool,
+    accept_input: bool,
+    on_line: Option<LineHandler>,
+    on_complete: F,
+) -> Result<String, DdevError>
+where
+    F: FnOnce(StreamingOutcome) + Send + 'static,
+{
     let process_id = generate_process_id();
     let command_name = command_name.to_string();
     let project_name = project_name.to_string();
@@ -264,17 +698,29 @@ pub fn run_ddev_command_streaming(
         },
     );
 
+    let command_line = format!("ddev {}", args.join(" "));
+
     // Spawn the command in a background thread
     thread::spawn(move || {
-        let result = Command::new(&ddev_cmd)
-            .args(&full_args)
-            .env("PATH", &enhanced_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn();
+        let started_at = Instant::now();
+        let mut command = Command::new(&ddev_cmd);
+        command.args(&full_args).env("PATH", &enhanced_path);
+        if accept_input {
+            command.stdin(Stdio::piped());
+        }
+        #[cfg(unix)]
+        if on_line.is_some() {
+            std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        }
+        let result = configure_output(&mut command, merge_output).and_then(|merged| {
+            let child = command.spawn();
+            // Drop our copies of the merged pipe's write end so reads end when the child exits
+            drop(command);
+            child.map(|child| (child, merged))
+        });
 
-        let mut child = match result {
-            Ok(child) => child,
+        let (mut child, merged) = match result {
+            Ok(spawned) => spawned,
             Err(e) => {
                 let _ = window.emit(
                     "command-status",
@@ -295,51 +741,32 @@ pub fn run_ddev_command_streaming(
 
         // Store child in registry BEFORE starting output threads
         // Use Some(child) since this is a single-command task
-        {
-            let mut registry = PROCESS_REGISTRY.lock().unwrap();
-            registry.insert(
-                process_id_clone.clone(),
-                crate::process::ProcessEntry {
-                    child: Some(child),
-                    command: command_name.clone(),
-                    project: project_name.clone(),
-                },
-            );
-        }
+        register_child_process(&process_id_clone, child, &command_name, &project_name);
 
-        // Clone window for stderr thread
-        let window_clone = window.clone();
+        let output = OutputEmitter::new(&window, &command_name, &project_name, command_line)
+            .with_line_handler(on_line);
 
         // Spawn thread for stdout
         let stdout_handle = stdout.map(|stdout| {
-            let window = window.clone();
+            let output = output.clone();
             thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().map_while(Result::ok) {
-                    let _ = window.emit(
-                        "command-output",
-                        CommandOutput {
-                            line,
-                            stream: "stdout".to_string(),
-                        },
-                    );
-                }
+                output.emit_segments(stdout, "stdout");
             })
         });
 
         // Spawn thread for stderr
         let stderr_handle = stderr.map(|stderr| {
+            let output = output.clone();
             thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().map_while(Result::ok) {
-                    let _ = window_clone.emit(
-                        "command-output",
-                        CommandOutput {
-                            line,
-                            stream: "stderr".to_string(),
-                        },
-                    );
-                }
+                output.emit_segments(stderr, "stderr");
+            })
+        });
+
+        // Single reader for the merged pipe, if any (child.stdout/stderr are None then)
+        let merged_handle = merged.map(|reader| {
+            let output = output.clone();
+            thread::spawn(move || {
+                output.emit_segments(reader, "stdout");
             })
         });
 
@@ -350,6 +777,9 @@ pub fn run_ddev_command_streaming(
         if let Some(handle) = stderr_handle {
             let _ = handle.join();
         }
+        if let Some(handle) = merged_handle {
+            let _ = handle.join();
+        }
 
         // Retrieve child from registry and wait for completion
         // For single-command tasks, we remove the entry entirely when done
@@ -360,6 +790,14 @@ pub fn run_ddev_command_streaming(
                 .and_then(|entry| entry.child.map(|mut child| child.wait()))
         };
 
+        if let Some(result) = &status {
+            on_complete(StreamingOutcome {
+                success: matches!(result, Ok(exit_status) if exit_status.success()),
+                elapsed: started_at.elapsed(),
+                tail: output.tail(),
+            });
+        }
+
         match status {
             Some(Ok(exit_status)) if exit_status.success() => {
                 let _ = window.emit(
@@ -402,6 +840,26 @@ pub fn run_ddev_command_streaming_in_dir(
     project_name: &str,
     args: &[&str],
     working_dir: &str,
+) -> Result<String, DdevError> {
+    run_ddev_command_streaming_in_dir_logged(
+        window,
+        command_name,
+        project_name,
+        args,
+        working_dir,
+        None,
+    )
+}
+
+/// Like `run_ddev_command_streaming_in_dir`, additionally writing stderr to `stderr_log`
+/// (truncated first) so failures can be reviewed after the output has scrolled away
+pub fn run_ddev_command_streaming_in_dir_logged(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    args: &[&str],
+    working_dir: &str,
+    stderr_log: Option<PathBuf>,
 ) -> Result<String, DdevError> {
     let process_id = generate_process_id();
     let command_name = command_name.to_string();
@@ -428,6 +886,8 @@ pub fn run_ddev_command_streaming_in_dir(
         },
     );
 
+    let command_line = format!("ddev {}", args.join(" "));
+
     // Spawn the command in a background thread
     thread::spawn(move || {
         let result = Command::new(&ddev_cmd)
@@ -459,48 +919,24 @@ pub fn run_ddev_command_streaming_in_dir(
         let stderr = child.stderr.take();
 
         // Store child in registry
-        {
-            let mut registry = PROCESS_REGISTRY.lock().unwrap();
-            registry.insert(
-                process_id_clone.clone(),
-                crate::process::ProcessEntry {
-                    child: Some(child),
-                    command: command_name.clone(),
-                    project: project_name.clone(),
-                },
-            );
-        }
+        register_child_process(&process_id_clone, child, &command_name, &project_name);
 
-        let window_clone = window.clone();
+        let mut output = OutputEmitter::new(&window, &command_name, &project_name, command_line);
+        if let Some(file) = stderr_log.and_then(|path| std::fs::File::create(path).ok()) {
+            output = output.with_stderr_log(file);
+        }
 
         let stdout_handle = stdout.map(|stdout| {
-            let window = window.clone();
+            let output = output.clone();
             thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().map_while(Result::ok) {
-                    let _ = window.emit(
-                        "command-output",
-                        CommandOutput {
-                            line,
-                            stream: "stdout".to_string(),
-                        },
-                    );
-                }
+                output.emit_segments(stdout, "stdout");
             })
         });
 
         let stderr_handle = stderr.map(|stderr| {
+            let output = output.clone();
             thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().map_while(Result::ok) {
-                    let _ = window_clone.emit(
-                        "command-output",
-                        CommandOutput {
-                            line,
-                            stream: "stderr".to_string(),
-                        },
-                    );
-                }
+                output.emit_segments(stderr, "stderr");
             })
         });
 
@@ -568,6 +1004,22 @@ pub async fn run_ddev_json_command_async<T: for<'de> Deserialize<'de>>(
     Ok(response.raw)
 }
 
+/// Run a DDEV command with JSON output from a project directory (async version)
+pub async fn run_ddev_json_command_in_dir_async<T: for<'de> Deserialize<'de>>(
+    args: &[&str],
+    dir: &str,
+) -> Result<T, DdevError> {
+    let mut full_args = vec!["--json-output"];
+    full_args.extend_from_slice(args);
+
+    let output = run_ddev_command_in_dir_async(&full_args, dir).await?;
+
+    let response: DdevJsonResponse<T> =
+        serde_json::from_str(&output).map_err(|e| DdevError::ParseError(e.to_string()))?;
+
+    Ok(response.raw)
+}
+
 /// Helper to run a command with streaming output
 /// If process_id is provided, registers the child process for cancellation support
 #[allow(clippy::too_many_arguments)]
@@ -581,6 +1033,32 @@ pub fn run_streaming_command(
     command_name: &str,
     project_name: &str,
 ) -> Result<bool, &'static str> {
+    run_streaming_command_with_outcome(
+        window,
+        cmd,
+        args,
+        cwd,
+        enhanced_path,
+        process_id,
+        command_name,
+        project_name,
+    )
+    .map(|outcome| outcome.success)
+}
+
+/// Like `run_streaming_command`, but returns the `StreamingOutcome` (exit status, elapsed
+/// time and last lines of output) so callers can report why a step failed
+#[allow(clippy::too_many_arguments)]
+pub fn run_streaming_command_with_outcome(
+    window: &Window,
+    cmd: &str,
+    args: &[&str],
+    cwd: &str,
+    enhanced_path: &str,
+    process_id: Option<&str>,
+    command_name: &str,
+    project_name: &str,
+) -> Result<StreamingOutcome, &'static str> {
     // Check if already cancelled before starting
     if let Some(pid) = process_id {
         if is_process_cancelled(pid) {
@@ -588,6 +1066,7 @@ pub fn run_streaming_command(
         }
     }
 
+    let started_at = Instant::now();
     let result = Command::new(cmd)
         .args(args)
         .current_dir(cwd)
@@ -599,14 +1078,16 @@ pub fn run_streaming_command(
     let mut child = match result {
         Ok(child) => child,
         Err(e) => {
+            let message = format!("Failed to start {}: {}", cmd, e);
             let _ = window.emit(
                 "command-output",
-                CommandOutput {
-                    line: format!("Failed to start {}: {}", cmd, e),
-                    stream: "stderr".to_string(),
-                },
+                CommandOutput::new(message.clone(), "stderr"),
             );
-            return Ok(false);
+            return Ok(StreamingOutcome {
+                success: false,
+                elapsed: started_at.elapsed(),
+                tail: vec![message],
+            });
         }
     };
 
@@ -618,36 +1099,24 @@ pub fn run_streaming_command(
         register_child_process(pid, child, command_name, project_name);
     }
 
-    let window_clone = window.clone();
+    let output = OutputEmitter::new(
+        window,
+        command_name,
+        project_name,
+        format!("{} {}", cmd, args.join(" ")),
+    );
 
     let stdout_handle = stdout.map(|stdout| {
-        let window = window.clone();
+        let output = output.clone();
         thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines().map_while(Result::ok) {
-                let _ = window.emit(
-                    "command-output",
-                    CommandOutput {
-                        line,
-                        stream: "stdout".to_string(),
-                    },
-                );
-            }
+            output.emit_segments(stdout, "stdout");
         })
     });
 
     let stderr_handle = stderr.map(|stderr| {
+        let output = output.clone();
         thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines().map_while(Result::ok) {
-                let _ = window_clone.emit(
-                    "command-output",
-                    CommandOutput {
-                        line,
-                        stream: "stderr".to_string(),
-                    },
-                );
-            }
+            output.emit_segments(stderr, "stderr");
         })
     });
 
@@ -660,24 +1129,26 @@ pub fn run_streaming_command(
 
     // Get the child back from registry and wait for it
     // The entry remains in the registry (with child=None) so is_process_cancelled still works
-    if let Some(pid) = process_id {
+    let success = if let Some(pid) = process_id {
         if let Some(mut child) = take_child_process(pid) {
-            match child.wait() {
-                Ok(status) => Ok(status.success()),
-                Err(_) => Ok(false),
-            }
+            child.wait().is_ok_and(|status| status.success())
         } else {
             // Either the entry was removed (cancelled) or child was already taken
             // Check if the entry still exists to determine which case
             if is_process_cancelled(pid) {
-                Err("cancelled")
-            } else {
-                // Entry exists but child was already taken - shouldn't happen normally
-                Ok(true)
+                return Err("cancelled");
             }
+            // Entry exists but child was already taken - shouldn't happen normally
+            true
         }
     } else {
         // No process_id, this shouldn't happen in our usage but handle it
-        Ok(true)
-    }
+        true
+    };
+
+    Ok(StreamingOutcome {
+        success,
+        elapsed: started_at.elapsed(),
+        tail: output.tail(),
+    })
 }
@@ -11,6 +11,10 @@ pub enum DdevError {
     NotInstalled,
     #[error("IO error: {0}")]
     IoError(String),
+    #[error("Docker is not running")]
+    DockerNotRunning,
+    #[error("Timed out: {0}")]
+    Timeout(String),
 }
 
 impl Serialize for DdevError {
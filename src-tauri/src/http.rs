@@ -0,0 +1,57 @@
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::error::DdevError;
+use crate::settings::load_settings;
+
+/// Default read timeout for outbound requests, when not set in settings
+const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+
+/// Connect timeout for outbound requests
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Shared client, built on first use and rebuilt after proxy/timeout settings change
+static HTTP_CLIENT: Lazy<Mutex<Option<reqwest::Client>>> = Lazy::new(|| Mutex::new(None));
+
+/// Get the shared HTTP client for outbound requests (add-on registry, schema, ...)
+/// Uses the proxy and timeout from settings; without a proxy, reqwest honors the system
+/// proxy variables
+pub fn http_client() -> Result<reqwest::Client, DdevError> {
+    let mut cached = HTTP_CLIENT.lock().unwrap();
+    if let Some(client) = cached.as_ref() {
+        return Ok(client.clone());
+    }
+
+    let client = build_http_client()?;
+    *cached = Some(client.clone());
+    Ok(client)
+}
+
+/// Drop the shared client so the next request picks up changed settings
+pub fn reset_http_client() {
+    *HTTP_CLIENT.lock().unwrap() = None;
+}
+
+fn build_http_client() -> Result<reqwest::Client, DdevError> {
+    let settings = load_settings();
+    let read_timeout = settings
+        .http_timeout_secs
+        .unwrap_or(DEFAULT_HTTP_TIMEOUT_SECS);
+
+    let mut builder = reqwest::Client::builder()
+        .user_agent(concat!("ddev-manager/", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(Duration::from_secs(read_timeout));
+
+    if let Some(proxy_url) = settings.http_proxy {
+        let proxy = reqwest::Proxy::all(&proxy_url).map_err(|e| {
+            DdevError::CommandFailed(format!("Invalid proxy URL {}: {}", proxy_url, e))
+        })?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder
+        .build()
+        .map_err(|e| DdevError::IoError(format!("Failed to build HTTP client: {}", e)))
+}
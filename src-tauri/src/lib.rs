@@ -1,18 +1,26 @@
 mod commands;
 mod ddev;
 mod error;
+mod fs_utils;
+mod http;
 mod process;
+mod pty;
 mod schema;
+mod settings;
 mod types;
 
 use commands::*;
 use process::cancel_command;
+use pty::send_stdin;
 use std::sync::Mutex;
 use tauri::menu::{
     AboutMetadata, CheckMenuItem, CheckMenuItemBuilder, MenuBuilder, MenuItemBuilder,
-    PredefinedMenuItem, SubmenuBuilder,
+    PredefinedMenuItem, Submenu, SubmenuBuilder,
 };
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+
+/// Menu id prefix for themes registered at runtime (`theme_custom:<theme id>`)
+pub const CUSTOM_THEME_MENU_PREFIX: &str = "theme_custom:";
 
 // Store theme menu items for updating check state
 pub struct ThemeMenuItems {
@@ -20,16 +28,58 @@ pub struct ThemeMenuItems {
     pub dark: CheckMenuItem<tauri::Wry>,
     pub high_contrast: CheckMenuItem<tauri::Wry>,
     pub system: CheckMenuItem<tauri::Wry>,
+    /// Appearance submenu, where registered themes are appended
+    pub appearance: Submenu<tauri::Wry>,
+    /// Themes added with `register_theme`: (theme id, label, menu item)
+    pub custom: Vec<(String, String, CheckMenuItem<tauri::Wry>)>,
+    /// Theme whose item is currently checked
+    pub current: String,
+}
+
+impl ThemeMenuItems {
+    /// Check the item for `theme` and uncheck all others
+    pub fn check(&mut self, theme: &str) {
+        let _ = self.light.set_checked(theme == "light");
+        let _ = self.dark.set_checked(theme == "dark");
+        let _ = self.high_contrast.set_checked(theme == "high-contrast");
+        let _ = self.system.set_checked(theme == "system");
+        for (id, _, item) in &self.custom {
+            let _ = item.set_checked(id == theme);
+        }
+        self.current = theme.to_string();
+    }
+}
+
+/// Map a theme menu item id to its theme ("theme_high_contrast" -> "high-contrast")
+fn theme_for_menu_id(event_id: &str) -> Option<String> {
+    if let Some(custom) = event_id.strip_prefix(CUSTOM_THEME_MENU_PREFIX) {
+        return Some(custom.to_string());
+    }
+    event_id
+        .strip_prefix("theme_")
+        .map(|builtin| builtin.replace('_', "-"))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Opt-in software rendering for Wayland/GBM setups where WebKitGTK fails even with
+    // compositing disabled. Must be set before the webview is created.
+    #[cfg(target_os = "linux")]
+    if settings::load_settings().software_rendering {
+        std::env::set_var("LIBGL_ALWAYS_SOFTWARE", "1");
+        std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
+    }
+
     #[allow(unused_mut)]
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .setup(|app| {
+            // Background auto-snapshot schedulers
+            app.manage(AutoSnapshotTasks::default());
+
             // Build the app menu (macOS application menu with About, etc.)
             let app_menu = SubmenuBuilder::new(app, "DDEV Manager")
                 .item(&PredefinedMenuItem::about(
@@ -67,21 +117,24 @@ pub fn run() {
                 .checked(true)
                 .build(app)?;
 
+            let appearance_menu = SubmenuBuilder::new(app, "Appearance")
+                .item(&theme_light)
+                .item(&theme_dark)
+                .item(&theme_high_contrast)
+                .item(&theme_system)
+                .build()?;
+
             // Store references for later updates
             app.manage(Mutex::new(ThemeMenuItems {
                 light: theme_light.clone(),
                 dark: theme_dark.clone(),
                 high_contrast: theme_high_contrast.clone(),
                 system: theme_system.clone(),
+                appearance: appearance_menu.clone(),
+                custom: Vec::new(),
+                current: "system".to_string(),
             }));
 
-            let appearance_menu = SubmenuBuilder::new(app, "Appearance")
-                .item(&theme_light)
-                .item(&theme_dark)
-                .item(&theme_high_contrast)
-                .item(&theme_system)
-                .build()?;
-
             let view_menu = SubmenuBuilder::new(app, "View")
                 .item(&zoom_in)
                 .item(&zoom_out)
@@ -98,25 +151,37 @@ pub fn run() {
 
             app.set_menu(menu)?;
 
+            // Reopen the main window where it was last closed
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = restore_window_state(window);
+            }
+
             // Ensure schema is updated in the background on startup
             schema::ensure_schema_updated();
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { .. } = event {
+                if let Some(window) = window.app_handle().get_webview_window(window.label()) {
+                    let _ = save_window_state(window);
+                }
+            }
+        })
         .on_menu_event(|app, event| {
             let event_id = event.id().as_ref();
 
             // Handle theme changes - update checkmarks using stored references
-            if event_id.starts_with("theme_") {
+            if let Some(theme) = theme_for_menu_id(event_id) {
                 if let Some(items) = app.try_state::<Mutex<ThemeMenuItems>>() {
-                    if let Ok(items) = items.lock() {
-                        let _ = items.light.set_checked(event_id == "theme_light");
-                        let _ = items.dark.set_checked(event_id == "theme_dark");
-                        let _ = items
-                            .high_contrast
-                            .set_checked(event_id == "theme_high_contrast");
-                        let _ = items.system.set_checked(event_id == "theme_system");
+                    if let Ok(mut items) = items.lock() {
+                        items.check(&theme);
                     }
                 }
+
+                // Registered themes are applied by whoever registered them
+                if event_id.starts_with(CUSTOM_THEME_MENU_PREFIX) {
+                    let _ = app.emit("theme-changed", theme);
+                }
             }
 
             // Execute JavaScript handlers
@@ -149,55 +214,167 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Projects
             list_projects,
+            list_projects_filtered,
+            list_projects_offline,
+            refresh_projects,
+            run_across_projects,
+            list_projects_ordered,
+            get_project_basic,
+            get_project_order,
+            set_project_order,
+            detect_hostname_conflicts,
             describe_project,
+            prewarm_describe,
             start_project,
+            first_start,
+            watch_project_boot,
             stop_project,
             restart_project,
+            project_needs_restart,
+            smart_restart,
             delete_project,
+            preview_delete,
             poweroff,
             change_php_version,
             change_nodejs_version,
+            set_router_ports,
+            set_timezone,
+            set_composer_version,
+            list_config_backups,
+            restore_config_backup,
+            set_router_option,
+            set_php_ini_override,
+            reconfigure_project,
             toggle_service,
+            toggle_xdebug,
+            generate_xdebug_ide_config,
+            get_published_ports,
+            detect_port_conflicts_global,
+            check_port_conflicts,
+            check_time_skew,
+            get_composer_scripts,
+            exec_command,
+            exec_interactive,
+            share_project,
+            get_start_times,
+            set_project_note,
+            get_project_note,
+            get_last_start_result,
+            get_orphaned_projects,
+            cleanup_orphaned_project,
+            regenerate_certs,
+            copy_all_urls,
+            get_primary_url,
+            get_image_info,
+            images_present,
+            pull_images,
+            // Hosting providers
+            list_providers,
+            pull_from_provider,
+            push_to_provider,
             // Snapshots
             list_snapshots,
             create_snapshot,
             restore_snapshot,
+            restore_latest_snapshot,
+            get_last_restore_log,
+            restore_snapshot_from_path,
+            start_auto_snapshot,
+            stop_auto_snapshot,
             delete_snapshot,
             cleanup_snapshots,
+            // Project templates
+            export_project_template,
+            apply_project_template,
+            list_config_presets,
+            apply_config_preset,
             // Database
             select_database_file,
             select_export_destination,
             import_db,
             export_db,
+            copy_database,
+            inspect_db_file,
+            open_db_shell,
+            open_db_console,
+            send_db_console_input,
+            get_db_credentials,
+            copy_db_password,
             // Logs
             get_logs,
+            pause_log_stream,
+            resume_log_stream,
+            get_recent_errors,
+            stream_access_log,
             // Utils
             check_ddev_installed,
             get_ddev_version,
+            get_app_info,
+            diagnose_tooling,
+            find_all_ddev_installs,
+            check_tls_cert,
+            get_graphics_environment,
+            set_software_rendering,
+            list_wsl_distros,
+            set_wsl_distro,
+            get_resolved_ddev_invocation,
+            rescan_environment,
+            set_http_proxy,
+            set_http_timeout,
             open_project_url,
             open_project_folder,
+            open_ddev_folder,
+            open_in_editor,
             sync_theme_menu,
+            register_theme,
+            get_theme_menu_state,
+            save_window_state,
+            restore_window_state,
+            // Global config
+            get_global_flags,
+            set_global_flag,
+            get_telemetry_status,
+            set_telemetry,
+            get_router_type,
+            set_router_type,
+            get_registered_project_paths,
+            get_ddev_cache_usage,
+            clear_ddev_cache,
             // Addons
             list_installed_addons,
             fetch_addon_registry,
+            refresh_addon_stats,
             install_addon,
+            verify_addon_installed,
+            update_addon,
+            get_addon_updates,
             remove_addon,
+            check_addon_compatible,
             // Process management
             cancel_command,
+            send_stdin,
             // Project creation
             select_folder,
             create_project,
+            resume_create,
             check_folder_empty,
+            normalize_path,
+            validate_project_layout,
+            is_path_allowed,
+            set_allowed_project_roots,
             check_composer_installed,
             check_wpcli_installed,
             // Screenshots
             capture_screenshot,
+            cancel_screenshot,
+            set_screenshot_concurrency,
             get_screenshot_path,
             get_screenshot_data,
             delete_screenshot,
             // Schema
             get_ddev_schema,
             refresh_ddev_schema,
+            clear_schema_cache,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
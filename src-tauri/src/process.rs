@@ -1,7 +1,9 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::process::Child;
-use std::sync::Mutex;
+use std::io::Write;
+use std::process::{Child, ChildStdin};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{Emitter, Window};
 
 use crate::error::DdevError;
@@ -14,6 +16,8 @@ pub struct ProcessEntry {
     pub child: Option<Child>,
     pub command: String,
     pub project: String,
+    /// The child's stdin, for commands started with a piped stdin (see `write_child_stdin`)
+    pub stdin: Option<Arc<Mutex<ChildStdin>>>,
 }
 
 // Global process registry - stores active child processes by ID
@@ -35,6 +39,14 @@ pub fn is_process_cancelled(process_id: &str) -> bool {
     !registry.contains_key(process_id)
 }
 
+/// Check if any of the given commands is currently registered for a project
+pub fn is_command_running(project: &str, commands: &[&str]) -> bool {
+    let registry = PROCESS_REGISTRY.lock().unwrap();
+    registry
+        .values()
+        .any(|entry| entry.project == project && commands.contains(&entry.command.as_str()))
+}
+
 /// Create an entry in the registry for a multi-step task (no active child yet)
 pub fn create_task_entry(process_id: &str, command: &str, project: &str) {
     let mut registry = PROCESS_REGISTRY.lock().unwrap();
@@ -44,13 +56,15 @@ pub fn create_task_entry(process_id: &str, command: &str, project: &str) {
             child: None,
             command: command.to_string(),
             project: project.to_string(),
+            stdin: None,
         },
     );
 }
 
 /// Store a child process in the registry for cancellation support
-/// Updates an existing entry or creates a new one
-pub fn register_child_process(process_id: &str, child: Child, command: &str, project: &str) {
+/// Updates an existing entry or creates a new one. A piped stdin is kept for `write_child_stdin`.
+pub fn register_child_process(process_id: &str, mut child: Child, command: &str, project: &str) {
+    let stdin = child.stdin.take().map(|stdin| Arc::new(Mutex::new(stdin)));
     let mut registry = PROCESS_REGISTRY.lock().unwrap();
     registry.insert(
         process_id.to_string(),
@@ -58,6 +72,7 @@ pub fn register_child_process(process_id: &str, child: Child, command: &str, pro
             child: Some(child),
             command: command.to_string(),
             project: project.to_string(),
+            stdin,
         },
     );
 }
@@ -68,12 +83,61 @@ pub fn register_child_process(process_id: &str, child: Child, command: &str, pro
 pub fn take_child_process(process_id: &str) -> Option<Child> {
     let mut registry = PROCESS_REGISTRY.lock().unwrap();
     if let Some(entry) = registry.get_mut(process_id) {
+        // Close stdin too, so a child waiting for input sees end of input before we wait on it
+        entry.stdin = None;
         entry.child.take()
     } else {
         None
     }
 }
 
+/// Write input to a registered child's stdin pipe
+/// Fails if the process has exited or was started without a piped stdin. The registry lock
+/// is released before writing, so a child that stops reading can't block other processes.
+pub fn write_child_stdin(process_id: &str, data: &str) -> Result<(), DdevError> {
+    let stdin = PROCESS_REGISTRY
+        .lock()
+        .unwrap()
+        .get(process_id)
+        .and_then(|entry| entry.stdin.clone())
+        .ok_or_else(|| {
+            DdevError::CommandFailed(format!("Process {} is not accepting input", process_id))
+        })?;
+
+    let mut stdin = stdin.lock().unwrap();
+    stdin
+        .write_all(data.as_bytes())
+        .and_then(|_| stdin.flush())
+        .map_err(|e| DdevError::IoError(format!("Failed to send input: {}", e)))
+}
+
+/// Wait for a registered child to exit by polling, without reading its pipes to EOF
+/// Useful when subprocesses (like docker exec) inherit stdout/stderr and keep them open
+/// after the child itself has exited. The entry is removed once the child exits.
+/// Returns None if the process was cancelled, otherwise whether it exited successfully
+pub async fn wait_for_registered_child(process_id: &str) -> Option<bool> {
+    loop {
+        {
+            let mut registry = PROCESS_REGISTRY.lock().unwrap();
+            let entry = registry.get_mut(process_id)?;
+            if let Some(child) = entry.child.as_mut() {
+                match child.try_wait() {
+                    Ok(Some(status)) => {
+                        registry.remove(process_id);
+                        return Some(status.success());
+                    }
+                    Ok(None) => {}
+                    Err(_) => {
+                        registry.remove(process_id);
+                        return Some(false);
+                    }
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
 /// Completely remove a task entry from the registry
 /// Call this when a multi-step task completes (success or error)
 pub fn remove_task_entry(process_id: &str) {
@@ -81,6 +145,22 @@ pub fn remove_task_entry(process_id: &str) {
     registry.remove(process_id);
 }
 
+/// Kill the process group led by `child`, if it was started in its own group
+/// (see `run_ddev_command_streaming_with_line_handler`), so its helpers exit with it
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    let pid = child.id() as libc::pid_t;
+    // SAFETY: plain syscalls on a pid we own; a child in our group has a different pgid
+    unsafe {
+        if libc::getpgid(pid) == pid {
+            libc::killpg(pid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_child: &Child) {}
+
 /// Cancel a running DDEV command by its process ID
 #[tauri::command]
 pub fn cancel_command(window: Window, process_id: String) -> Result<(), DdevError> {
@@ -89,6 +169,7 @@ pub fn cancel_command(window: Window, process_id: String) -> Result<(), DdevErro
     if let Some(entry) = registry.remove(&process_id) {
         // Kill the process if there's an active one
         if let Some(mut child) = entry.child {
+            kill_process_group(&child);
             // Ignore errors - process might have already exited
             let _ = child.kill();
             // Wait for process to actually terminate (cleanup)
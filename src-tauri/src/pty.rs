@@ -0,0 +1,177 @@
+use once_cell::sync::Lazy;
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, PtySize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{Emitter, Window};
+
+use crate::ddev::read_segments;
+use crate::error::DdevError;
+use crate::process::{
+    create_task_entry, generate_process_id, is_process_cancelled, remove_task_entry,
+    write_child_stdin,
+};
+use crate::types::{CommandOutput, CommandStatus};
+
+/// Terminal size reported to PTY commands (the terminal panel wraps long lines itself)
+const PTY_SIZE: PtySize = PtySize {
+    rows: 40,
+    cols: 160,
+    pixel_width: 0,
+    pixel_height: 0,
+};
+
+/// Input side of a running PTY command
+struct PtySession {
+    // Shared so `send_stdin` can write without holding the sessions lock
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+}
+
+// Running PTY commands by process ID. Each also has a task entry in the process registry,
+// so `cancel_command` works the same as for piped commands.
+static PTY_SESSIONS: Lazy<Mutex<HashMap<String, PtySession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn pty_error(e: impl std::fmt::Display) -> DdevError {
+    DdevError::IoError(format!("Failed to start terminal: {}", e))
+}
+
+/// Run a command attached to a pseudo-terminal (non-blocking)
+/// Programs see a real TTY, so prompts and interactive installers behave as in a terminal.
+/// Output is emitted as `command-output` line by line as it arrives; input is sent with
+/// `send_stdin`. Returns a process ID that can be cancelled.
+pub fn run_pty_command(
+    window: Window,
+    command_name: &str,
+    project_name: &str,
+    cmd: &str,
+    args: &[&str],
+    cwd: &str,
+    enhanced_path: &str,
+) -> Result<String, DdevError> {
+    let pair = native_pty_system().openpty(PTY_SIZE).map_err(pty_error)?;
+
+    let mut command = CommandBuilder::new(cmd);
+    command.args(args);
+    command.cwd(cwd);
+    command.env("PATH", enhanced_path);
+
+    let mut child = pair.slave.spawn_command(command).map_err(pty_error)?;
+    // Only the child may hold the slave side, so reads end once it exits
+    drop(pair.slave);
+
+    let reader = pair.master.try_clone_reader().map_err(pty_error)?;
+    let writer = pair.master.take_writer().map_err(pty_error)?;
+
+    let process_id = generate_process_id();
+    let command_name = command_name.to_string();
+    let project_name = project_name.to_string();
+
+    create_task_entry(&process_id, &command_name, &project_name);
+    PTY_SESSIONS.lock().unwrap().insert(
+        process_id.clone(),
+        PtySession {
+            writer: Arc::new(Mutex::new(writer)),
+            killer: child.clone_killer(),
+        },
+    );
+
+    let _ = window.emit(
+        "command-status",
+        CommandStatus {
+            command: command_name.clone(),
+            project: project_name.clone(),
+            status: "started".to_string(),
+            message: Some(format!("Running: {} {}", cmd, args.join(" "))),
+            process_id: Some(process_id.clone()),
+        },
+    );
+
+    let output_window = window.clone();
+    let reader_handle = thread::spawn(move || emit_pty_output(&output_window, reader));
+
+    let process_id_clone = process_id.clone();
+    thread::spawn(move || {
+        // Poll so a cancellation (task entry removed) can kill the child
+        let status = loop {
+            if is_process_cancelled(&process_id_clone) {
+                if let Some(mut session) = PTY_SESSIONS.lock().unwrap().remove(&process_id_clone) {
+                    let _ = session.killer.kill();
+                }
+                let _ = child.wait();
+                break None;
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status.success()),
+                Ok(None) => thread::sleep(Duration::from_millis(100)),
+                Err(_) => break Some(false),
+            }
+        };
+
+        // Closing the master (held by the session writer) ends the reader on all platforms
+        PTY_SESSIONS.lock().unwrap().remove(&process_id_clone);
+        drop(pair.master);
+        let _ = reader_handle.join();
+
+        // Cancelled - cancel_command already emitted the status
+        let Some(success) = status else {
+            return;
+        };
+        remove_task_entry(&process_id_clone);
+        let _ = window.emit(
+            "command-status",
+            CommandStatus {
+                command: command_name,
+                project: project_name,
+                status: if success { "finished" } else { "error" }.to_string(),
+                message: Some(
+                    if success {
+                        "Command completed successfully"
+                    } else {
+                        "Command failed"
+                    }
+                    .to_string(),
+                ),
+                process_id: None,
+            },
+        );
+    });
+
+    Ok(process_id)
+}
+
+/// Forward PTY output as `command-output` events until the terminal closes
+/// Output is buffered across reads so lines (and multi-byte characters) split between reads
+/// stay whole; see `read_segments` for how "\r" redraws are reported.
+fn emit_pty_output(window: &Window, reader: Box<dyn Read + Send>) {
+    read_segments(reader, |line, is_progress| {
+        let mut output = CommandOutput::new(line, "stdout");
+        output.is_progress = is_progress;
+        let _ = window.emit("command-output", output);
+    });
+}
+
+/// Send input to a running command: a terminal session (`exec_interactive`) or the stdin pipe
+/// of a streaming command started with input enabled, e.g. to answer an addon install prompt
+/// `data` is written as-is: include "\n" to submit a line, or control characters like "\u{3}"
+/// (control characters only have an effect in a terminal)
+#[tauri::command]
+pub fn send_stdin(process_id: String, data: String) -> Result<(), DdevError> {
+    let writer = PTY_SESSIONS
+        .lock()
+        .unwrap()
+        .get(&process_id)
+        .map(|session| session.writer.clone());
+    let Some(writer) = writer else {
+        return write_child_stdin(&process_id, &data);
+    };
+
+    let mut writer = writer.lock().unwrap();
+    writer
+        .write_all(data.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(|e| DdevError::IoError(format!("Failed to send input: {}", e)))
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use crate::error::DdevError;
+use crate::fs_utils::write_atomic;
+use crate::http::http_client;
 
 const SCHEMA_URL: &str =
     "https://raw.githubusercontent.com/ddev/ddev/master/pkg/ddevapp/schema.json";
@@ -167,7 +169,9 @@ fn is_schema_stale(path: &PathBuf) -> bool {
 
 /// Fetch the schema from GitHub
 pub async fn fetch_schema() -> Result<DdevSchema, DdevError> {
-    let response = reqwest::get(SCHEMA_URL)
+    let response = http_client()?
+        .get(SCHEMA_URL)
+        .send()
         .await
         .map_err(|e| DdevError::CommandFailed(format!("Failed to fetch schema: {}", e)))?;
 
@@ -220,21 +224,41 @@ pub fn load_cached_schema() -> Result<DdevSchema, DdevError> {
 }
 
 /// Save the schema to cache
-fn save_schema(schema: &DdevSchema, path: &PathBuf) -> Result<(), DdevError> {
+fn save_schema(schema: &DdevSchema, path: &Path) -> Result<(), DdevError> {
     let content = serde_json::to_string_pretty(schema)
         .map_err(|e| DdevError::ParseError(format!("Failed to serialize schema: {}", e)))?;
 
-    fs::write(path, content)
+    write_atomic(path, content)
         .map_err(|e| DdevError::IoError(format!("Failed to write schema cache: {}", e)))?;
 
     Ok(())
 }
 
+/// Delete the cached schema file so the next lookup re-fetches it
+pub fn remove_cached_schema() -> Result<(), DdevError> {
+    let path = get_schema_path()?;
+
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(DdevError::IoError(format!(
+            "Failed to remove schema cache: {}",
+            e
+        ))),
+    }
+}
+
 /// Get the schema, preferring cache but falling back to fetch or hardcoded
 pub async fn get_schema() -> DdevSchema {
     // Try to load from cache first
-    if let Ok(schema) = load_cached_schema() {
-        return schema;
+    match load_cached_schema() {
+        Ok(schema) => return schema,
+        // A corrupt cache (e.g. truncated write) would otherwise never be replaced,
+        // since staleness is only judged by age
+        Err(DdevError::ParseError(_)) => {
+            let _ = remove_cached_schema();
+        }
+        Err(_) => {}
     }
 
     // Try to fetch from GitHub
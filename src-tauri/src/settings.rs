@@ -0,0 +1,176 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::DdevError;
+use crate::fs_utils::write_atomic;
+
+const SETTINGS_FILENAME: &str = "settings.json";
+const START_HISTORY_LIMIT: usize = 10;
+
+/// Persisted app settings and per-project bookkeeping
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Recent `ddev start` durations in milliseconds per project, oldest first
+    pub start_times: HashMap<String, Vec<u64>>,
+    /// Maximum headless browsers capturing screenshots at once (None = default)
+    pub screenshot_concurrency: Option<usize>,
+    /// Force software rendering for the webview on Linux (applied at next launch)
+    pub software_rendering: bool,
+    /// User-defined project list order by name (projects not listed sort after these)
+    pub project_order: Vec<String>,
+    /// WSL distro to run DDEV in on Windows (None = the default distro)
+    pub wsl_distro: Option<String>,
+    /// Proxy URL for the app's own HTTP requests (None = system proxy settings)
+    pub http_proxy: Option<String>,
+    /// Read timeout in seconds for the app's own HTTP requests (None = default)
+    pub http_timeout_secs: Option<u64>,
+    /// Extra folders (besides home) new projects may be created in
+    pub allowed_project_roots: Vec<String>,
+    /// Main window geometry from the last session (None = platform default placement)
+    pub window_state: Option<WindowState>,
+    /// Outcome of each project's most recent `ddev start` from the app
+    pub last_start_results: HashMap<String, StartResult>,
+    /// Free-form notes per project, shown on the project card (may span several lines)
+    pub project_notes: HashMap<String, String>,
+}
+
+/// Outcome of a project's most recent start
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartResult {
+    /// "succeeded", "failed", or "unknown" when the project hasn't been started from the app
+    pub status: String,
+    /// Last error lines of the start output, for failed starts
+    pub error_summary: Option<String>,
+}
+
+/// Saved main window geometry in physical pixels
+/// Position and size are the last un-maximized bounds, so un-maximizing restores them
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+}
+
+// Serializes read-modify-write cycles on the settings file
+static SETTINGS_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Get the path to the settings file
+fn get_settings_path() -> Result<PathBuf, DdevError> {
+    let data_dir = dirs::data_dir()
+        .ok_or_else(|| DdevError::IoError("Could not determine app data directory".to_string()))?;
+
+    let app_dir = data_dir.join("ddev-manager");
+    if !app_dir.exists() {
+        fs::create_dir_all(&app_dir).map_err(|e| {
+            DdevError::IoError(format!("Failed to create app data directory: {}", e))
+        })?;
+    }
+
+    Ok(app_dir.join(SETTINGS_FILENAME))
+}
+
+/// Read settings from disk; a missing file gives the defaults
+/// A file that exists but can't be read or parsed is an error, so it's never mistaken for
+/// "no settings" and overwritten
+fn read_settings(path: &Path) -> Result<Settings, DdevError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Settings::default()),
+        Err(e) => {
+            return Err(DdevError::IoError(format!(
+                "Failed to read settings: {}",
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&content)
+        .map_err(|e| DdevError::ParseError(format!("Failed to parse settings: {}", e)))
+}
+
+/// Load settings from disk, falling back to defaults if missing or unreadable
+pub fn load_settings() -> Settings {
+    get_settings_path()
+        .and_then(|path| read_settings(&path))
+        .unwrap_or_default()
+}
+
+/// Move an unparseable settings file aside (as `settings.json.corrupt-<millis>`) so it can be
+/// recovered by hand instead of being overwritten
+fn move_corrupt_settings(path: &Path) -> Result<(), DdevError> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let backup = path.with_file_name(format!("{}.corrupt-{}", SETTINGS_FILENAME, millis));
+    fs::rename(path, &backup)
+        .map_err(|e| DdevError::IoError(format!("Failed to move aside unreadable settings: {}", e)))
+}
+
+/// Save settings to disk
+fn save_settings(settings: &Settings) -> Result<(), DdevError> {
+    let path = get_settings_path()?;
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| DdevError::ParseError(format!("Failed to serialize settings: {}", e)))?;
+
+    write_atomic(&path, content)
+        .map_err(|e| DdevError::IoError(format!("Failed to write settings: {}", e)))?;
+
+    Ok(())
+}
+
+/// Apply a change to the settings and persist it
+pub fn update_settings<F>(change: F) -> Result<(), DdevError>
+where
+    F: FnOnce(&mut Settings),
+{
+    let _guard = SETTINGS_LOCK.lock().unwrap();
+    let path = get_settings_path()?;
+    let mut settings = match read_settings(&path) {
+        Ok(settings) => settings,
+        // Start over from the defaults, keeping the corrupt file next to the new one
+        Err(DdevError::ParseError(_)) => {
+            move_corrupt_settings(&path)?;
+            Settings::default()
+        }
+        Err(e) => return Err(e),
+    };
+    change(&mut settings);
+    save_settings(&settings)
+}
+
+/// Remember the outcome of a project's latest start, replacing the previous one
+pub fn record_start_result(project: &str, result: StartResult) -> Result<(), DdevError> {
+    update_settings(|settings| {
+        settings
+            .last_start_results
+            .insert(project.to_string(), result);
+    })
+}
+
+/// Drop a deleted project's note so it doesn't reappear on a new project with the same name
+pub fn remove_project_note(project: &str) -> Result<(), DdevError> {
+    update_settings(|settings| {
+        settings.project_notes.remove(project);
+    })
+}
+
+/// Record how long a project took to start, keeping only the most recent entries
+pub fn record_start_time(project: &str, elapsed_ms: u64) -> Result<(), DdevError> {
+    update_settings(|settings| {
+        let history = settings.start_times.entry(project.to_string()).or_default();
+        history.push(elapsed_ms);
+        if history.len() > START_HISTORY_LIMIT {
+            let excess = history.len() - START_HISTORY_LIMIT;
+            history.drain(..excess);
+        }
+    })
+}
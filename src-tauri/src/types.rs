@@ -1,5 +1,6 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Helper to deserialize a field that can be null or an array into Vec<T>
 pub fn deserialize_null_as_empty_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
@@ -11,11 +12,47 @@ where
     Ok(opt.unwrap_or_default())
 }
 
+// Sequence number for the next `CommandOutput`, shared by all reader threads
+static OUTPUT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
 /// Event payload for command output
 #[derive(Clone, Serialize)]
 pub struct CommandOutput {
     pub line: String,
     pub stream: String, // "stdout" or "stderr"
+    /// Increases with every line read, across stdout and stderr, so the frontend can restore
+    /// the order lines were printed in when events from the two streams arrive interleaved
+    pub seq: u64,
+    /// A progress redraw ended by a bare "\r": the next line from the stream replaces it
+    pub is_progress: bool,
+}
+
+impl CommandOutput {
+    /// Create an output line, numbering it in read order; call as soon as the line is read
+    pub fn new(line: String, stream: &str) -> Self {
+        Self {
+            line,
+            stream: stream.to_string(),
+            seq: OUTPUT_SEQUENCE.fetch_add(1, Ordering::SeqCst),
+            is_progress: false,
+        }
+    }
+}
+
+/// Event payload when a command is stuck on a sudo password prompt it can't show
+#[derive(Clone, Serialize)]
+pub struct NeedsSudo {
+    pub command: String,
+    pub project: String,
+    pub line: String,
+    pub guidance: String,
+}
+
+/// Event payload when `ddev share` reports the public URL of its tunnel
+#[derive(Clone, Serialize)]
+pub struct ShareUrl {
+    pub project: String,
+    pub url: String,
 }
 
 /// Event payload for command status
@@ -64,6 +101,40 @@ pub struct DdevProjectBasic {
     pub nodejs_version: String,
 }
 
+/// Optional filter for choosing which projects a bulk command runs in
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProjectFilter {
+    #[serde(rename = "type")]
+    pub project_type: Option<String>,
+    pub status: Option<String>,
+}
+
+/// A hostname claimed by more than one project (the router can only route it to one)
+#[derive(Debug, Serialize, Clone)]
+pub struct HostnameConflict {
+    pub hostname: String,
+    pub projects: Vec<String>,
+}
+
+/// What `ddev delete` would remove for a project, shown before confirming
+#[derive(Debug, Serialize, Clone)]
+pub struct DeletePreview {
+    pub containers: Vec<String>,
+    pub volumes: Vec<String>,
+    /// Project files are never touched by `ddev delete`
+    pub keeps_files: bool,
+    /// `.ddev/db_snapshots` has snapshots, which survive the delete (`--omit-snapshot`)
+    pub has_snapshots: bool,
+}
+
+/// Result of a project list refresh; `partial` means Docker was down and the list came from
+/// the offline fallback (no status or URLs)
+#[derive(Debug, Serialize, Clone)]
+pub struct ProjectListRefresh {
+    pub projects: Vec<DdevProjectBasic>,
+    pub partial: bool,
+}
+
 /// Host port mapping
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HostPortMapping {
@@ -89,6 +160,89 @@ pub struct DdevService {
     pub virtual_host: Option<String>,
 }
 
+/// A host port published by one of a project's services
+#[derive(Debug, Serialize, Clone)]
+pub struct PublishedPort {
+    pub service: String,
+    pub exposed: String,
+    pub host: String,
+}
+
+/// A project service publishing a host port
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PortClaim {
+    pub project: String,
+    pub service: String,
+}
+
+/// A host port published by more than one service across running projects
+#[derive(Debug, Serialize, Clone)]
+pub struct PortConflict {
+    pub port: String,
+    pub services: Vec<PortClaim>,
+}
+
+/// Whether a host port a project publishes is free before starting it
+#[derive(Debug, Serialize, Clone)]
+pub struct PortCheck {
+    pub port: u16,
+    pub service: String,
+    /// Held by something other than this project's own containers
+    pub in_use: bool,
+}
+
+/// Runtime Xdebug state after a toggle
+#[derive(Debug, Serialize, Clone)]
+pub struct XdebugState {
+    pub enabled: bool,
+}
+
+/// Images used by a project's web and db containers
+/// Digests are those of the pulled base images; None when an image has none locally
+#[derive(Debug, Serialize, Clone)]
+pub struct ImageInfo {
+    pub web_image: String,
+    pub web_digest: Option<String>,
+    pub db_image: String,
+    pub db_digest: Option<String>,
+}
+
+/// Whether a project's images are already downloaded (a first start after pulling is slow)
+#[derive(Debug, Serialize, Clone)]
+pub struct ImagesPresent {
+    pub web: bool,
+    pub db: bool,
+}
+
+/// Progress through the stages of a multi-step task, emitted as `task-phase`
+#[derive(Debug, Serialize, Clone)]
+pub struct TaskPhase {
+    pub process_id: String,
+    pub command: String,
+    pub project: String,
+    /// Stage identifier, e.g. "images", "pull", "start", "ready"
+    pub phase: String,
+    pub message: String,
+}
+
+/// Clock difference between the host and a project's web container
+#[derive(Debug, Serialize, Clone)]
+pub struct TimeSkew {
+    pub host_time: String,
+    pub container_time: String,
+    /// Container time minus host time (positive = container clock is ahead)
+    pub skew_seconds: i64,
+    /// Set when the skew is large enough to break TLS or caching
+    pub warning: Option<String>,
+}
+
+/// A script defined in a project's composer.json
+#[derive(Debug, Serialize, Clone)]
+pub struct ComposerScript {
+    pub name: String,
+    pub command: String,
+}
+
 /// Database information
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DdevDatabaseInfo {
@@ -143,6 +297,12 @@ pub struct DdevProjectDetails {
     pub webserver_type: Option<String>,
     pub database_type: Option<String>,
     pub database_version: Option<String>,
+    /// Version reported by the running database server (not part of `ddev describe`)
+    #[serde(default)]
+    pub database_version_runtime: Option<String>,
+    /// True when the running server doesn't match the configured `database_version`
+    #[serde(default)]
+    pub database_version_mismatch: bool,
     pub performance_mode: Option<String>,
     pub webimg: Option<String>,
     pub dbimg: Option<String>,
@@ -275,6 +435,52 @@ pub struct RegistryAddon {
     pub stars: i32,
 }
 
+/// Live GitHub stats for an addon repository (fresher than the registry snapshot)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddonStats {
+    #[serde(alias = "stargazers_count")]
+    pub stars: i32,
+    pub updated_at: String,
+}
+
+/// Event payload after an addon install, telling whether the addon is now registered
+#[derive(Clone, Serialize)]
+pub struct AddonVerified {
+    pub project: String,
+    pub addon: String,
+    pub installed: bool,
+}
+
+/// An installed addon with a newer release in the registry
+#[derive(Debug, Serialize, Clone)]
+pub struct AddonUpdateInfo {
+    pub name: String,
+    pub repository: String,
+    pub installed_version: Option<String>,
+    pub latest_version: String,
+}
+
+/// Whether an addon's DDEV version constraint is satisfied by the installed DDEV
+#[derive(Debug, Serialize, Clone)]
+pub struct AddonCompatibility {
+    pub compatible: bool,
+    pub constraint: String,
+    pub installed: String,
+}
+
+/// Manifest stored in a project template bundle alongside the `.ddev/` folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectTemplateManifest {
+    pub project_type: String,
+    pub php_version: Option<String>,
+    pub webserver_type: Option<String>,
+    pub database_type: Option<String>,
+    pub database_version: Option<String>,
+    pub nodejs_version: String,
+    /// Add-on repositories (e.g. "ddev/ddev-redis") to reinstall
+    pub addons: Vec<String>,
+}
+
 /// Registry response structure from addons.ddev.com
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AddonRegistry {
@@ -294,6 +500,13 @@ pub struct LogOutput {
     pub service: String,
 }
 
+/// Event payload for a slow query found in a project's db logs
+#[derive(Clone, Serialize)]
+pub struct SlowQuery {
+    pub seconds: f64,
+    pub query: String,
+}
+
 /// Status structure for log streaming
 #[derive(Clone, Serialize)]
 pub struct LogStatus {
@@ -304,15 +517,190 @@ pub struct LogStatus {
     pub process_id: Option<String>,
 }
 
+/// A request from a project's web server access log
+/// Lines that don't parse as common/combined log format only carry `raw`
+#[derive(Clone, Serialize)]
+pub struct AccessLogEntry {
+    pub project: String,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub status: Option<u16>,
+    /// Request duration, when the log format includes it
+    pub ms: Option<u64>,
+    pub raw: String,
+}
+
+/// Version and build metadata for the app itself
+#[derive(Debug, Serialize, Clone)]
+pub struct AppInfo {
+    pub version: String,
+    pub tauri_version: String,
+    pub target_triple: String,
+    pub commit: Option<String>,
+}
+
+/// A database snapshot from `ddev snapshot --list`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SnapshotInfo {
+    #[serde(alias = "Name")]
+    pub name: String,
+    /// Creation timestamp as reported by DDEV (RFC 3339)
+    #[serde(alias = "Created", default)]
+    pub created: String,
+    /// Archive size, when DDEV reports one
+    #[serde(alias = "Size", default)]
+    pub size: Option<String>,
+}
+
+/// Event payload emitted after an automatic snapshot is taken
+#[derive(Clone, Serialize)]
+pub struct SnapshotCreated {
+    pub project: String,
+    pub snapshot: String,
+    pub pruned: Vec<String>,
+}
+
+/// Event payload emitted after `ddev poweroff`, listing any DDEV containers still present
+#[derive(Clone, Serialize)]
+pub struct PoweroffComplete {
+    pub residual_count: usize,
+    pub containers: Vec<String>,
+}
+
+/// A ddev binary found on this machine
+#[derive(Debug, Serialize, Clone)]
+pub struct DdevInstall {
+    pub path: String,
+    pub version: String,
+}
+
+/// Event payload emitted when a service's status changes while a project boots
+#[derive(Clone, Serialize)]
+pub struct ServiceStatus {
+    pub project: String,
+    pub service: String,
+    pub status: String,
+    pub healthy: bool,
+}
+
+/// Disk usage of one entry in DDEV's global `~/.ddev` directory
+#[derive(Debug, Serialize, Clone)]
+pub struct DdevCacheEntry {
+    pub name: String,
+    pub bytes: u64,
+    pub clearable: bool,
+}
+
+/// TLS certificate details for a URL, and whether a strict client trusts it
+#[derive(Debug, Serialize, Clone)]
+pub struct TlsCertInfo {
+    pub valid_chain: bool,
+    pub self_signed: bool,
+    pub expires: Option<String>,
+    pub issuer: Option<String>,
+}
+
+/// Graphics/webview configuration the app was started with (for diagnostics)
+#[derive(Debug, Serialize, Clone)]
+pub struct GraphicsEnvironment {
+    pub session_type: String,
+    pub compositing_disabled: bool,
+    pub software_rendering: bool,
+    pub renderer_hint: String,
+}
+
+/// Exactly how the app runs DDEV on this machine: `command base_args... <args>` with PATH set
+/// to `enhanced_path`
+#[derive(Debug, Serialize, Clone)]
+pub struct DdevInvocation {
+    pub command: String,
+    pub base_args: Vec<String>,
+    pub enhanced_path: String,
+}
+
+/// Fresh snapshot of the external tools the app depends on
+#[derive(Debug, Serialize, Clone)]
+pub struct EnvironmentReport {
+    pub ddev_installed: bool,
+    /// `ddev --version` output, when DDEV is installed
+    pub ddev_version: Option<String>,
+    pub docker_running: bool,
+    /// Resolved path per tool (see `diagnose_tooling`)
+    pub tools: HashMap<String, Option<String>>,
+    pub invocation: DdevInvocation,
+}
+
+/// An installed WSL distro and whether DDEV and Docker are usable inside it
+#[derive(Debug, Serialize, Clone)]
+pub struct WslDistro {
+    pub name: String,
+    pub has_ddev: bool,
+    pub has_docker: bool,
+}
+
+/// A shared DDEV config snippet that can be applied as a `.ddev/config.<name>.yaml` override
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigPreset {
+    pub name: String,
+    pub path: String,
+}
+
+/// An automatic copy of `.ddev/config.yaml` taken before a config change
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigBackup {
+    /// File name under `.ddev/.backups`, passed back to `restore_config_backup`
+    pub name: String,
+    /// Unix timestamp (milliseconds) when the backup was taken
+    pub created_at: u64,
+}
+
+/// A theme registered in the Appearance menu at runtime
+#[derive(Debug, Serialize, Clone)]
+pub struct ThemeOption {
+    pub id: String,
+    pub label: String,
+}
+
+/// Current state of the Appearance menu
+#[derive(Debug, Serialize, Clone)]
+pub struct ThemeMenuState {
+    /// Checked theme ("light", "dark", "high-contrast", "system" or a registered id)
+    pub current: String,
+    pub custom_themes: Vec<ThemeOption>,
+}
+
 /// Status structure for screenshot capture
 #[derive(Clone, Serialize)]
 pub struct ScreenshotStatus {
     pub project: String,
-    pub status: String, // "started", "capturing", "finished", "error"
+    pub status: String, // "queued", "started", "capturing", "finished", "error", "cancelled"
     pub path: Option<String>,
     pub message: Option<String>,
 }
 
+/// Result of peeking at a database dump before importing it
+#[derive(Debug, Serialize, Clone)]
+pub struct DbFileInfo {
+    pub compressed: bool,
+    pub likely_engine: Option<String>, // "mysql", "mariadb" or "postgres"
+    pub approx_uncompressed_size: Option<u64>,
+}
+
+/// A user-entered path after cleanup, with what exists there
+#[derive(Debug, Serialize, Clone)]
+pub struct NormalizedPath {
+    pub path: String,
+    pub exists: bool,
+    pub is_dir: bool,
+}
+
+/// Whether a folder is an acceptable place for a new project, and why not
+#[derive(Debug, Serialize, Clone)]
+pub struct PathCheck {
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
 /// CMS installation instruction
 #[derive(Debug, Deserialize)]
 pub struct CmsInstall {
@@ -9,6 +9,8 @@ import { Tooltip } from "@/components/ui/Tooltip";
 interface CommandOutput {
   line: string;
   stream: "stdout" | "stderr";
+  seq: number;
+  is_progress: boolean;
 }
 
 interface CommandStatus {
@@ -40,7 +42,14 @@ export function StatusBar({ onToggleTerminal, isTerminalOpen }: StatusBarProps)
 
   // Listen for command events
   useEffect(() => {
+    // Highest seq shown, so a line delivered late doesn't replace a newer one
+    let lastSeq = -1;
     const unlistenOutput = listen<CommandOutput>("command-output", (event) => {
+      const { seq } = event.payload;
+      if (seq !== undefined) {
+        if (seq <= lastSeq) return;
+        lastSeq = seq;
+      }
       // Only update if we have a non-empty line
       const line = event.payload.line.trim();
       if (line) {
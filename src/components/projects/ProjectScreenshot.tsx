@@ -8,7 +8,7 @@ import { cn } from "@/lib/utils";
 
 interface ScreenshotStatus {
   project: string;
-  status: "started" | "capturing" | "finished" | "error";
+  status: "queued" | "started" | "capturing" | "finished" | "error" | "cancelled";
   path: string | null;
   message: string | null;
 }
@@ -50,6 +50,7 @@ export function ProjectScreenshot({ projectName, primaryUrl, isRunning }: Projec
       if (event.payload.project !== projectName) return;
 
       switch (event.payload.status) {
+        case "queued":
         case "started":
         case "capturing":
           setIsCapturing(true);
@@ -69,6 +70,11 @@ export function ProjectScreenshot({ projectName, primaryUrl, isRunning }: Projec
           setIsCapturing(false);
           setError(event.payload.message || "Screenshot capture failed");
           break;
+        case "cancelled":
+          // Keep the message so auto-capture doesn't immediately start again
+          setIsCapturing(false);
+          setError(event.payload.message || "Screenshot cancelled");
+          break;
       }
     });
 
@@ -14,14 +14,6 @@ describe("SnapshotsSection", () => {
     approot: "/home/user/projects/my-project",
   };
 
-  const mockSnapshotsResponse = (snapshots: Array<{ Name: string; Created: string }> | null) =>
-    JSON.stringify({
-      level: "info",
-      msg: "snapshot list",
-      raw: { "my-project": snapshots },
-      time: "2024-01-15T10:00:00Z",
-    });
-
   beforeEach(() => {
     vi.clearAllMocks();
   });
@@ -29,7 +21,7 @@ describe("SnapshotsSection", () => {
   describe("empty state", () => {
     it("should show empty state when no snapshots exist", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(null),
+        list_snapshots: [],
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -43,7 +35,7 @@ describe("SnapshotsSection", () => {
 
     it("should not show Clean All button when no snapshots", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(null),
+        list_snapshots: [],
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -56,13 +48,13 @@ describe("SnapshotsSection", () => {
 
   describe("with snapshots", () => {
     const mockSnapshots = [
-      { Name: "snapshot-1", Created: "2024-01-15T10:00:00Z" },
-      { Name: "snapshot-2", Created: "2024-01-14T09:00:00Z" },
+      { name: "snapshot-1", created: "2024-01-15T10:00:00Z", size: null },
+      { name: "snapshot-2", created: "2024-01-14T09:00:00Z", size: null },
     ];
 
     it("should display list of snapshots", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -75,7 +67,7 @@ describe("SnapshotsSection", () => {
 
     it("should show Clean All button when snapshots exist", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -87,7 +79,7 @@ describe("SnapshotsSection", () => {
 
     it("should show Restore button for each snapshot", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -107,7 +99,7 @@ describe("SnapshotsSection", () => {
   describe("create snapshot", () => {
     it("should render create snapshot form", async () => {
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(null),
+        list_snapshots: [],
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -120,7 +112,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(null),
+        list_snapshots: [],
         create_snapshot: "Created snapshot",
       });
 
@@ -140,7 +132,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(null),
+        list_snapshots: [],
         create_snapshot: "Created snapshot",
       });
 
@@ -159,13 +151,13 @@ describe("SnapshotsSection", () => {
   });
 
   describe("restore snapshot", () => {
-    const mockSnapshots = [{ Name: "snapshot-1", Created: "2024-01-15T10:00:00Z" }];
+    const mockSnapshots = [{ name: "snapshot-1", created: "2024-01-15T10:00:00Z", size: null }];
 
     it("should show restore confirmation dialog", async () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -188,7 +180,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
         restore_snapshot: "Restored",
       });
 
@@ -219,7 +211,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -245,13 +237,13 @@ describe("SnapshotsSection", () => {
   });
 
   describe("delete snapshot", () => {
-    const mockSnapshots = [{ Name: "snapshot-1", Created: "2024-01-15T10:00:00Z" }];
+    const mockSnapshots = [{ name: "snapshot-1", created: "2024-01-15T10:00:00Z", size: null }];
 
     it("should show delete confirmation dialog", async () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -270,7 +262,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
         delete_snapshot: "Deleted",
       });
 
@@ -294,15 +286,15 @@ describe("SnapshotsSection", () => {
 
   describe("cleanup all snapshots", () => {
     const mockSnapshots = [
-      { Name: "snapshot-1", Created: "2024-01-15T10:00:00Z" },
-      { Name: "snapshot-2", Created: "2024-01-14T09:00:00Z" },
+      { name: "snapshot-1", created: "2024-01-15T10:00:00Z", size: null },
+      { name: "snapshot-2", created: "2024-01-14T09:00:00Z", size: null },
     ];
 
     it("should show cleanup confirmation dialog", async () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
       });
 
       render(<SnapshotsSection {...defaultProps} />);
@@ -321,7 +313,7 @@ describe("SnapshotsSection", () => {
       const user = userEvent.setup();
 
       setupInvokeMock(vi.mocked(invoke), {
-        list_snapshots: mockSnapshotsResponse(mockSnapshots),
+        list_snapshots: mockSnapshots,
         cleanup_snapshots: "Cleaned up",
       });
 
@@ -60,6 +60,62 @@ describe("Terminal", () => {
       expect(screen.getByText("Container started")).toBeInTheDocument();
     });
 
+    it("should replace progress lines with the next line", async () => {
+      let outputCallback: ((event: unknown) => void) | null = null;
+
+      vi.mocked(listen).mockImplementation((event, callback) => {
+        if (event === "command-output") {
+          outputCallback = callback as (event: unknown) => void;
+        }
+        return Promise.resolve(() => {});
+      });
+
+      render(<Terminal isOpen={true} />);
+
+      await act(async () => {
+        outputCallback?.({
+          payload: { line: "Pulling 10%", stream: "stdout", is_progress: true },
+        });
+        outputCallback?.({
+          payload: { line: "Pulling 60%", stream: "stdout", is_progress: true },
+        });
+        outputCallback?.({
+          payload: { line: "Pull complete", stream: "stdout", is_progress: false },
+        });
+      });
+
+      expect(screen.queryByText("Pulling 10%")).not.toBeInTheDocument();
+      expect(screen.queryByText("Pulling 60%")).not.toBeInTheDocument();
+      expect(screen.getByText("Pull complete")).toBeInTheDocument();
+    });
+
+    it("should order output lines by sequence number", async () => {
+      let outputCallback: ((event: unknown) => void) | null = null;
+
+      vi.mocked(listen).mockImplementation((event, callback) => {
+        if (event === "command-output") {
+          outputCallback = callback as (event: unknown) => void;
+        }
+        return Promise.resolve(() => {});
+      });
+
+      render(<Terminal isOpen={true} />);
+
+      await act(async () => {
+        outputCallback?.({ payload: { line: "third", stream: "stdout", seq: 3 } });
+        outputCallback?.({ payload: { line: "first", stream: "stderr", seq: 1 } });
+        outputCallback?.({ payload: { line: "second", stream: "stdout", seq: 2 } });
+        // Duplicate delivery of an already shown line
+        outputCallback?.({ payload: { line: "second", stream: "stdout", seq: 2 } });
+      });
+
+      const texts = ["first", "second", "third"].map((text) => screen.getAllByText(text));
+      expect(texts.map((matches) => matches.length)).toEqual([1, 1, 1]);
+      const [first, second, third] = texts.map(([element]) => element);
+      expect(first.compareDocumentPosition(second) & Node.DOCUMENT_POSITION_FOLLOWING).toBeTruthy();
+      expect(second.compareDocumentPosition(third) & Node.DOCUMENT_POSITION_FOLLOWING).toBeTruthy();
+    });
+
     it("should display stderr with different styling", async () => {
       let outputCallback: ((event: unknown) => void) | null = null;
 
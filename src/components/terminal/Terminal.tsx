@@ -7,6 +7,8 @@ import { Tooltip } from "@/components/ui/Tooltip";
 interface CommandOutput {
   line: string;
   stream: "stdout" | "stderr";
+  seq: number;
+  is_progress: boolean;
 }
 
 interface CommandStatus {
@@ -22,6 +24,10 @@ interface TerminalLine {
   text: string;
   type: "stdout" | "stderr" | "status" | "info";
   timestamp: Date;
+  // Progress redraw (ended with "\r") that the next line of the same stream replaces
+  progress?: boolean;
+  // Read order of command output across stdout and stderr (absent for status lines)
+  seq?: number;
 }
 
 interface TerminalProps {
@@ -35,14 +41,34 @@ export function Terminal({ isOpen }: TerminalProps) {
   const scrollRef = useRef<HTMLDivElement>(null);
   const lineIdRef = useRef(0);
 
-  const addLine = (text: string, type: TerminalLine["type"]) => {
+  const addLine = (
+    text: string,
+    type: TerminalLine["type"],
+    { progress = false, seq }: { progress?: boolean; seq?: number } = {}
+  ) => {
     const newLine: TerminalLine = {
       id: lineIdRef.current++,
       text,
       type,
       timestamp: new Date(),
+      progress,
+      seq,
     };
-    setLines((prev) => [...prev, newLine]);
+    setLines((prev) => {
+      // stdout and stderr events can arrive out of order: insert after the last output line
+      // read before this one. Status lines have no seq, so lines never move past them.
+      let index = prev.length;
+      while (seq !== undefined && index > 0) {
+        const before = prev[index - 1];
+        if (before.seq === undefined || before.seq < seq) break;
+        if (before.seq === seq) return prev;
+        index--;
+      }
+
+      const previous = prev[index - 1];
+      const replaced = previous?.progress && previous.type === type ? 1 : 0;
+      return [...prev.slice(0, index - replaced), newLine, ...prev.slice(index)];
+    });
   };
 
   const clearLines = () => {
@@ -59,7 +85,8 @@ export function Terminal({ isOpen }: TerminalProps) {
   // Listen for command output events
   useEffect(() => {
     const unlistenOutput = listen<CommandOutput>("command-output", (event) => {
-      addLine(event.payload.line, event.payload.stream);
+      const { line, stream, seq, is_progress } = event.payload;
+      addLine(line, stream, { progress: is_progress, seq });
     });
 
     const unlistenStatus = listen<CommandStatus>("command-status", (event) => {
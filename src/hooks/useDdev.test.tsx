@@ -345,17 +345,10 @@ describe("useDdev hooks", () => {
 
   describe("useListSnapshots", () => {
     it("should fetch and parse snapshots successfully", async () => {
-      const mockResponse = JSON.stringify({
-        level: "info",
-        msg: "snapshot list",
-        raw: {
-          "my-project": [
-            { Name: "snapshot-1", Created: "2024-01-15T10:00:00Z" },
-            { Name: "snapshot-2", Created: "2024-01-14T09:00:00Z" },
-          ],
-        },
-        time: "2024-01-15T10:00:00Z",
-      });
+      const mockResponse = [
+        { name: "snapshot-1", created: "2024-01-15T10:00:00Z", size: null },
+        { name: "snapshot-2", created: "2024-01-14T09:00:00Z", size: null },
+      ];
 
       setupInvokeMock(vi.mocked(invoke), {
         list_snapshots: mockResponse,
@@ -373,12 +366,7 @@ describe("useDdev hooks", () => {
     });
 
     it("should return empty array when no snapshots exist", async () => {
-      const mockResponse = JSON.stringify({
-        level: "info",
-        msg: "snapshot list",
-        raw: { "my-project": null },
-        time: "2024-01-15T10:00:00Z",
-      });
+      const mockResponse: unknown[] = [];
 
       setupInvokeMock(vi.mocked(invoke), {
         list_snapshots: mockResponse,
@@ -18,12 +18,22 @@ export const queryKeys = {
   snapshots: (project: string) => ["snapshots", project] as const,
 };
 
+// Whether the describe cache has been warmed for this session
+let describeCachePrewarmed = false;
+
 // Fetch all projects
 export function useProjects() {
   return useQuery({
     queryKey: queryKeys.projects,
     queryFn: async () => {
       const projects = await invoke<DdevProjectBasic[]>("list_projects");
+      if (!describeCachePrewarmed) {
+        describeCachePrewarmed = true;
+        // Warm the describe cache in the background so detail panels open instantly
+        invoke("prewarm_describe", {
+          names: projects.map((p) => p.name),
+        }).catch(() => {});
+      }
       return projects;
     },
     refetchInterval: 5000, // Refresh every 5 seconds
@@ -32,12 +42,17 @@ export function useProjects() {
 
 // Fetch a single project's details
 export function useProject(name: string | null) {
+  const queryClient = useQueryClient();
+
   return useQuery({
     queryKey: queryKeys.project(name ?? ""),
     queryFn: async () => {
       if (!name) return null;
+      // Accept a cached describe on first load; later refetches always get fresh data
+      const useCache = queryClient.getQueryData(queryKeys.project(name)) === undefined;
       const project = await invoke<DdevProjectDetails>("describe_project", {
         name,
+        useCache,
       });
       return project;
     },
@@ -182,42 +197,19 @@ export function useOpenFolder() {
   });
 }
 
-// Snapshot types for parsing DDEV output
-interface DdevSnapshotRaw {
-  Name: string;
-  Created: string;
-}
-
-interface DdevSnapshotResponse {
-  level: string;
-  msg: string;
-  raw: Record<string, DdevSnapshotRaw[] | null>;
-  time: string;
-}
-
 export interface Snapshot {
   name: string;
   created: string;
+  size: string | null;
 }
 
-// List snapshots for a project
+// List snapshots for a project (newest first)
 export function useListSnapshots(project: string | null) {
   return useQuery({
     queryKey: queryKeys.snapshots(project ?? ""),
     queryFn: async (): Promise<Snapshot[]> => {
       if (!project) return [];
-      const output = await invoke<string>("list_snapshots", { project });
-      try {
-        const parsed: DdevSnapshotResponse = JSON.parse(output);
-        const snapshots = parsed.raw[project];
-        if (!snapshots) return [];
-        return snapshots.map((s) => ({
-          name: s.Name,
-          created: s.Created,
-        }));
-      } catch {
-        return [];
-      }
+      return invoke<Snapshot[]>("list_snapshots", { project });
     },
     enabled: !!project,
   });
@@ -314,6 +306,7 @@ export function useImportDb() {
         filePath,
         database: options?.database,
         noDrop: options?.noDrop,
+        createIfMissing: options?.createIfMissing,
       });
     },
   });
@@ -373,7 +366,7 @@ export function useChangePhpVersion() {
   });
 }
 
-// Toggle a DDEV service on/off (async, waits for completion)
+// Toggle a DDEV service on/off (async, waits for completion, cancellable via process ID)
 export function useToggleService() {
   const queryClient = useQueryClient();
   const { open, autoOpen } = useTerminalStore();
@@ -391,6 +384,7 @@ export function useToggleService() {
       enable: boolean;
     }) => {
       if (autoOpen) open();
+      // Resolves once the command finishes; the process ID comes from the "started" event
       return invoke<void>("toggle_service", { name, approot, service, enable });
     },
     onSuccess: (_data, variables) => {
@@ -113,6 +113,7 @@ export const defaultInvokeHandlers = {
   get_ddev_version: "v1.23.0",
   list_projects: [createMockProjectBasic()],
   describe_project: createMockProjectDetails(),
+  prewarm_describe: undefined,
   start_project: undefined,
   stop_project: undefined,
   restart_project: undefined,
@@ -121,6 +122,6 @@ export const defaultInvokeHandlers = {
   open_project_folder: undefined,
   create_snapshot: "snapshot-20240101-120000",
   restore_snapshot: "Snapshot restored successfully",
-  list_snapshots: "",
+  list_snapshots: [],
   toggle_service: undefined,
 };
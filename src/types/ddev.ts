@@ -103,6 +103,8 @@ export interface DdevProjectDetails extends DdevProjectBasic {
   webserver_type: string;
   database_type: string;
   database_version: string;
+  database_version_runtime?: string | null;
+  database_version_mismatch?: boolean;
   performance_mode: string;
   webimg: string;
   dbimg: string;
@@ -213,6 +215,7 @@ export interface SortOption {
 export interface DbImportOptions {
   database?: string;
   noDrop?: boolean;
+  createIfMissing?: boolean;
 }
 
 // Database export options